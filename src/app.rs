@@ -1,59 +1,159 @@
-use eframe::egui;
+use eframe::egui::{self, accesskit::Role};
 use egui_commonmark::CommonMarkCache;
+use std::borrow::Cow;
 use std::sync::mpsc::Receiver;
+use std::sync::Arc;
 
-use crate::artwork::{Artwork, ArtworksResponse};
-use crate::components::{sidebar::Sidebar, settings::SettingsModal, ThemeMode};
+use crate::artwork::{self, Artwork, IndexError};
+use crate::bidi;
+use crate::components::{
+    a11y, sidebar::Sidebar, settings::SettingsModal, Action, Command, CommandPalette, FontLoader,
+    Keymap, MotionMode, PaletteEntry, ShortcutsOverlay, ThemeMode, Typography,
+};
 use crate::config::Config;
-use crate::pages::{HomePage, AboutPage, ArtworksPage, ResumePage};
+use crate::fetch::{Fetch, FetchLoader, FetchResult, HttpFetch};
+use crate::i18n::I18n;
+use crate::links::{LinkResolver, LinkTarget};
+use crate::pages::{
+    ArtworksPage, BlogPage, BlogState, LinkCodes, LinksPage, MarkdownPage, PageContent, PdfExport,
+    ResumeLayout, ResumeNavigation, ResumePage, ResumeSearch,
+};
+use crate::resume::{ResumeDocument, ResumeFormat};
 use crate::routes::Route;
+use crate::sanitize::Sanitizer;
 
 pub struct MeguiApp {
     config: Config,
     current_route: Route,
     theme_mode: ThemeMode,
+    motion_mode: MotionMode,
+    typography: Typography,
+    font_loader: FontLoader,
+    keymap: Keymap,
+    /// Locale code chosen in settings, or `i18n::AUTO`
+    locale: String,
+    i18n: I18n,
+    fetcher: Arc<dyn Fetch>,
 
     // Artworks state
     pub artworks: Vec<Artwork>,
     pub loading: bool,
     pub error: Option<String>,
     pub selected_artworks: Vec<Artwork>,
-    pub fetch_receiver: Option<Receiver<ehttp::Result<ehttp::Response>>>,
+    pub search_query: String,
+    pub fetch_receiver: Option<Receiver<FetchResult>>,
 
     // Resume state
-    resume_content: Option<String>,
+    resume_content: Option<ResumeDocument>,
     resume_loading: bool,
-    resume_receiver: Option<Receiver<ehttp::Result<ehttp::Response>>>,
+    resume_receiver: Option<Receiver<FetchResult>>,
     markdown_cache: CommonMarkCache,
+    resume_layout: ResumeLayout,
+    resume_navigation: ResumeNavigation,
+    resume_search: ResumeSearch,
+    resume_export: PdfExport,
+    link_resolver: LinkResolver,
+    /// Fetched content of `[[pages]]` entries, by page index
+    page_contents: Vec<PageContent>,
+    blog: BlogState,
+    link_codes: LinkCodes,
 
     // UI state
+    /// Menu and settings are out of reach
+    kiosk: bool,
     sidebar_open: bool,
     settings_open: bool,
+    shortcuts_open: bool,
+    command_palette: CommandPalette,
+}
+
+/// How the app starts; set from the command line on native
+#[derive(Default)]
+pub struct StartOptions {
+    pub config: Config,
+    /// Route hash such as `#/resume/experience`; the page URL is used when unset
+    pub route: Option<String>,
+    /// Hide the menu and settings, for unattended displays
+    pub kiosk: bool,
 }
 
 impl Default for MeguiApp {
     fn default() -> Self {
-        let config = Config::default();
+        Self::with_fetcher(Arc::new(HttpFetch))
+    }
+}
+
+impl MeguiApp {
+    /// The app with all its requests going through `fetcher`
+    pub fn with_fetcher(fetcher: Arc<dyn Fetch>) -> Self {
+        Self::with_options(StartOptions::default(), fetcher)
+    }
+
+    pub fn with_options(options: StartOptions, fetcher: Arc<dyn Fetch>) -> Self {
+        let config = options.config;
         let theme_mode = ThemeMode::from_str(&config.app.default_theme);
+        let motion_mode = MotionMode::from_str(&config.app.default_motion);
+        let typography = Typography::from_config(&config.typography);
+        let font_loader = FontLoader::new(&config.fonts);
+        let keymap = Keymap::from_config(&config.shortcuts);
+        let link_resolver = LinkResolver::new(&config.app.resume, &config.app.website);
+        let locale = config.app.default_locale.clone();
+        let i18n = I18n::new(&locale, &config);
 
-        // Check for initial route from URL hash
-        let initial_route = Route::get_from_url();
+        // Initial route from the options or the URL hash
+        let initial_route = match &options.route {
+            Some(hash) => Route::from_hash(hash, &config).unwrap_or_else(|| Route::home(&config)),
+            None => Route::get_from_url(&config),
+        };
+        let page_contents = config.pages.iter().map(|_| PageContent::default()).collect();
+        let link_codes = LinkCodes::new(&config.links);
+        // `#/resume/<section>` or `#/blog/<post>`
+        let initial_section = match &options.route {
+            Some(hash) => Route::split_section(hash).1.map(|section| section.to_string()),
+            None => Route::get_section_from_url(),
+        };
+        let (scroll_to, selected_post) = match initial_route {
+            Route::Blog => (None, initial_section),
+            _ => (initial_section, None),
+        };
 
         let mut app = Self {
             config,
             current_route: initial_route,
             theme_mode,
+            motion_mode,
+            typography,
+            font_loader,
+            keymap,
+            locale,
+            i18n,
+            fetcher,
             artworks: Vec::new(),
             loading: false,
             error: None,
             selected_artworks: Vec::new(),
+            search_query: String::new(),
             fetch_receiver: None,
-            sidebar_open: true,
+            kiosk: options.kiosk,
+            sidebar_open: !options.kiosk,
             settings_open: false,
+            shortcuts_open: false,
+            command_palette: CommandPalette::default(),
             resume_content: None,
             resume_loading: false,
             resume_receiver: None,
             markdown_cache: CommonMarkCache::default(),
+            resume_layout: ResumeLayout::default(),
+            resume_navigation: ResumeNavigation {
+                scroll_to,
+                active: None,
+            },
+            resume_search: ResumeSearch::default(),
+            resume_export: PdfExport::default(),
+            link_resolver,
+            page_contents,
+            blog: BlogState::new(selected_post),
+            link_codes,
         };
 
         // Auto-fetch artworks on startup
@@ -61,19 +161,60 @@ impl Default for MeguiApp {
 
         app
     }
-}
 
-impl MeguiApp {
+    pub fn new(cc: &eframe::CreationContext<'_>, options: StartOptions, fetcher: Arc<dyn Fetch>) -> Self {
+        let mut app = Self::with_options(options, fetcher);
+
+        // Restore persisted typography and motion choices
+        if let Some(storage) = cc.storage {
+            if let Some(typography) = eframe::get_value::<Typography>(storage, Typography::STORAGE_KEY) {
+                app.typography = typography.clamped();
+            }
+            if let Some(motion_mode) = eframe::get_value::<MotionMode>(storage, MotionMode::STORAGE_KEY) {
+                app.motion_mode = motion_mode;
+            }
+            if let Some(locale) = eframe::get_value::<String>(storage, I18n::STORAGE_KEY) {
+                app.set_locale(locale);
+            }
+        }
+
+        // Zoom shortcuts are handled by `Typography` so the choice is persisted
+        cc.egui_ctx.options_mut(|options| options.zoom_with_keyboard = false);
+
+        // Custom fonts from config
+        app.font_loader.start(&cc.egui_ctx, app.fetcher.as_ref());
+
+        // Images are decoded by egui_extras but fetched like everything else,
+        // so they are recorded and replayed too
+        egui_extras::install_image_loaders(&cc.egui_ctx);
+        cc.egui_ctx.add_bytes_loader(Arc::new(FetchLoader::new(app.fetcher.clone())));
+
+        // Deep links straight to the resume need it fetched
+        if app.current_route == Route::Resume {
+            ResumePage::start_fetch(
+                &cc.egui_ctx,
+                &app.config,
+                app.fetcher.as_ref(),
+                &mut app.resume_loading,
+                &mut app.resume_receiver,
+            );
+        }
+
+        app
+    }
+
+    /// Switch UI language; fetched pages are loaded again in the new locale
+    fn set_locale(&mut self, locale: String) {
+        self.i18n = I18n::new(&locale, &self.config);
+        self.locale = locale;
+        self.page_contents = self.config.pages.iter().map(|_| PageContent::default()).collect();
+    }
+
     fn start_artworks_fetch(&mut self) {
         self.loading = true;
         self.error = None;
-        let (sender, receiver) = std::sync::mpsc::channel();
-        self.fetch_receiver = Some(receiver);
-
-        let artworks_url = self.config.app.artworks.clone();
-        ehttp::fetch(ehttp::Request::get(&artworks_url), move |result| {
-            let _ = sender.send(result);
-        });
+        // No repaint needed: `update` keeps repainting while a fetch is pending
+        self.fetch_receiver = Some(self.fetcher.receive(&self.config.app.artworks, None));
     }
 
     fn process_fetch_response(&mut self) {
@@ -85,24 +226,21 @@ impl MeguiApp {
                 match result {
                     Ok(response) => {
                         if let Some(text) = response.text() {
-                            match serde_json::from_str::<Vec<ArtworksResponse>>(text) {
-                                Ok(data) => {
-                                    if let Some(first) = data.first() {
-                                        self.artworks = first.contents.clone();
-                                    } else {
-                                        self.error = Some("No data returned".to_string());
-                                    }
+                            match artwork::parse_index(text) {
+                                Ok(artworks) => self.artworks = artworks,
+                                Err(IndexError::Empty) => {
+                                    self.error = Some(self.i18n.tr("artworks-no-data").to_string());
                                 }
-                                Err(e) => {
-                                    self.error = Some(format!("Parse error: {}", e));
+                                Err(IndexError::Invalid(e)) => {
+                                    self.error = Some(self.i18n.tr_args("artworks-parse-error", &[("error", &e)]));
                                 }
                             }
                         } else {
-                            self.error = Some("Failed to read response text".to_string());
+                            self.error = Some(self.i18n.tr_args("read-failed", &[("url", &self.config.app.artworks)]));
                         }
                     }
                     Err(e) => {
-                        self.error = Some(format!("Fetch error: {}", e));
+                        self.error = Some(self.i18n.tr_args("fetch-error", &[("error", &e)]));
                     }
                 }
             }
@@ -117,56 +255,287 @@ impl MeguiApp {
 
                 match result {
                     Ok(response) => {
-                        if let Some(html) = response.text() {
-                            // Strip script and style tags before converting to markdown
-                            let cleaned_html = Self::strip_script_and_style_tags(html);
-                            // Convert HTML to Markdown
-                            let markdown = html2md::parse_html(&cleaned_html);
-                            self.resume_content = Some(markdown);
-                            self.current_route = Route::Resume;
+                        if let Some(text) = response.text() {
+                            let format = ResumeFormat::from_str(&self.config.app.resume_format);
+                            let sanitizer = Sanitizer::new(&self.config.sanitize, &self.config.app.resume);
+                            match ResumeDocument::parse(format, text, &sanitizer, &self.link_resolver) {
+                                Ok(document) => {
+                                    self.resume_content = Some(document);
+                                    self.resume_layout.document_changed();
+                                    self.current_route = Route::Resume;
+                                }
+                                Err(e) => {
+                                    self.error = Some(self.i18n.tr_args("resume-parse-error", &[("error", &e)]));
+                                }
+                            }
                         } else {
-                            self.error = Some("Failed to read resume".to_string());
+                            self.error = Some(self.i18n.tr("resume-read-failed").to_string());
                         }
                     }
                     Err(e) => {
-                        self.error = Some(format!("Resume fetch error: {}", e));
+                        self.error = Some(self.i18n.tr_args("resume-fetch-error", &[("error", &e)]));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Switch to a route, fetching the resume first if needed
+    fn navigate(&mut self, ctx: &egui::Context, route: Route) {
+        if route == Route::Resume && self.resume_content.is_none() {
+            // The route switches once the resume has loaded
+            self.start_resume_fetch(ctx);
+        } else {
+            if route == Route::Blog {
+                self.blog.selected = None;
+            }
+            self.current_route = route;
+        }
+        self.current_route.update_browser_url(&self.config);
+    }
+
+    /// Fetch the resume unless a fetch is already under way
+    fn start_resume_fetch(&mut self, ctx: &egui::Context) {
+        if !self.resume_loading {
+            ResumePage::start_fetch(
+                ctx,
+                &self.config,
+                self.fetcher.as_ref(),
+                &mut self.resume_loading,
+                &mut self.resume_receiver,
+            );
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        for action in self.keymap.poll(ctx) {
+            match action {
+                Action::GoHome => self.navigate(ctx, Route::home(&self.config)),
+                Action::GoArtworks => self.navigate(ctx, Route::Artworks),
+                Action::GoResume => self.navigate(ctx, Route::Resume),
+                Action::GoAbout => {
+                    if let Some(route) = Route::from_slug("about", &self.config) {
+                        self.navigate(ctx, route);
+                    }
+                }
+                // Find in the resume when reading it, otherwise search artworks
+                Action::FocusSearch if self.current_route == Route::Resume => {
+                    ctx.memory_mut(|mem| mem.request_focus(ResumeSearch::search_id()));
+                }
+                Action::FocusSearch => {
+                    self.navigate(ctx, Route::Artworks);
+                    ctx.memory_mut(|mem| mem.request_focus(ArtworksPage::search_id()));
+                }
+                Action::ToggleSidebar | Action::OpenSettings if self.kiosk => {}
+                Action::ToggleSidebar => self.sidebar_open = !self.sidebar_open,
+                Action::OpenSettings => self.settings_open = true,
+                Action::CloseWindow => {
+                    // Escape closes the overlays by itself
+                    if !self.shortcuts_open && !self.command_palette.open {
+                        ArtworksPage::close_topmost_window(ctx, &mut self.selected_artworks);
                     }
                 }
+                Action::ShowShortcuts => self.shortcuts_open = !self.shortcuts_open,
+                Action::CommandPalette => self.command_palette.toggle(),
             }
         }
     }
 
-    fn strip_script_and_style_tags(html: &str) -> String {
-        // Use ammonia to sanitize HTML
-        // By default, ammonia removes script and style tags along with their content
-        // and uses a whitelist-based approach to only keep safe HTML tags
-        ammonia::clean(html)
+    fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let mut entries: Vec<PaletteEntry> = Route::all(&self.config)
+            .into_iter()
+            .map(|route| {
+                PaletteEntry::new(
+                    self.i18n.tr_args("palette-go-to", &[("page", &route.title(&self.config, &self.i18n))]),
+                    Command::Navigate(route),
+                )
+            })
+            .collect();
+
+        for artwork in &self.artworks {
+            let mut entry = PaletteEntry::new(
+                self.i18n.tr_args("palette-artwork", &[("name", &artwork.name)]),
+                Command::OpenArtwork(artwork.name.clone()),
+            );
+            if let Some(info) = self.i18n.artwork_info(artwork) {
+                entry = entry.with_detail(bidi::visual(info));
+            }
+            entries.push(entry);
+        }
+
+        if let Some(resume) = &self.resume_content {
+            for heading in resume.outline(&self.i18n) {
+                entries.push(PaletteEntry::new(
+                    self.i18n.tr_args("palette-resume-section", &[("section", &heading.text)]),
+                    Command::ResumeSection(heading.slug),
+                ));
+            }
+        }
+
+        for post in self.blog.posts() {
+            let mut entry = PaletteEntry::new(
+                format!("{}: {}", Route::Blog.title(&self.config, &self.i18n), bidi::visual(&post.title)),
+                Command::BlogPost(post.slug.clone()),
+            );
+            if let Some(summary) = &post.summary {
+                entry = entry.with_detail(summary);
+            }
+            entries.push(entry);
+        }
+
+        for theme in [ThemeMode::Auto, ThemeMode::Light, ThemeMode::Dark, ThemeMode::HighContrast] {
+            entries.push(PaletteEntry::new(
+                self.i18n.tr_args("palette-theme", &[("theme", &theme.label(&self.i18n))]),
+                Command::SetTheme(theme),
+            ));
+        }
+
+        entries.push(PaletteEntry::new(
+            self.i18n.tr("palette-open-artworks-website"),
+            Command::OpenArtworksWebsite,
+        ));
+        entries.push(
+            PaletteEntry::new(self.i18n.tr("palette-reload"), Command::ReloadData)
+                .with_detail(self.i18n.tr("palette-reload-detail")),
+        );
+
+        entries
+    }
+
+    /// Keep clicks on resume anchors and links into this app inside the app.
+    /// Links opened in a new tab are left to the browser.
+    fn handle_content_links(&mut self, ctx: &egui::Context) {
+        let mut targets = Vec::new();
+        ctx.output_mut(|output| {
+            output.commands.retain_mut(|command| match command {
+                egui::OutputCommand::OpenUrl(open_url) => match self.link_resolver.target(&open_url.url, &self.config) {
+                    LinkTarget::External(url) => {
+                        open_url.url = url;
+                        true
+                    }
+                    target if !open_url.new_tab => {
+                        targets.push(target);
+                        false
+                    }
+                    _ => {
+                        open_url.url = self.link_resolver.resolve(&open_url.url);
+                        true
+                    }
+                },
+                _ => true,
+            });
+        });
+
+        for target in targets {
+            match target {
+                // Bare anchors only point into the resume
+                LinkTarget::Section(slug) if self.current_route == Route::Resume => {
+                    self.run_command(ctx, Command::ResumeSection(slug));
+                }
+                LinkTarget::Section(_) => {}
+                LinkTarget::Route(Route::Resume, Some(slug)) => {
+                    self.run_command(ctx, Command::ResumeSection(slug));
+                }
+                LinkTarget::Route(Route::Blog, Some(slug)) => {
+                    self.run_command(ctx, Command::BlogPost(slug));
+                }
+                LinkTarget::Route(route, _) => self.navigate(ctx, route),
+                LinkTarget::External(_) => {}
+            }
+        }
+    }
+
+    fn run_command(&mut self, ctx: &egui::Context, command: Command) {
+        match command {
+            Command::Navigate(route) => self.navigate(ctx, route),
+            Command::OpenArtwork(name) => {
+                if !self.selected_artworks.iter().any(|a| a.name == name) {
+                    if let Some(artwork) = self.artworks.iter().find(|a| a.name == name) {
+                        self.selected_artworks.push(artwork.clone());
+                    }
+                }
+            }
+            Command::ResumeSection(slug) => {
+                self.resume_navigation.scroll_to = Some(slug);
+                self.navigate(ctx, Route::Resume);
+            }
+            Command::BlogPost(slug) => {
+                self.navigate(ctx, Route::Blog);
+                Route::Blog.update_browser_url_with_section(&self.config, Some(&slug));
+                self.blog.selected = Some(slug);
+            }
+            Command::SetTheme(theme) => self.theme_mode = theme,
+            Command::OpenArtworksWebsite => {
+                let artworks_base = self.config.app.artworks.trim_end_matches("/index.json");
+                ctx.open_url(egui::OpenUrl::new_tab(artworks_base));
+            }
+            Command::ReloadData => {
+                self.start_artworks_fetch();
+                // Pages and blog posts are fetched again when next shown
+                self.page_contents = self.config.pages.iter().map(|_| PageContent::default()).collect();
+                self.blog = BlogState::new(self.blog.selected.take());
+                ctx.forget_all_images();
+
+                self.resume_content = None;
+                self.resume_layout.document_changed();
+                if self.current_route == Route::Resume {
+                    self.start_resume_fetch(ctx);
+                }
+            }
+        }
     }
 
     fn render_sidebar(&mut self, ctx: &egui::Context) {
-        egui::SidePanel::left("sidebar")
+        let side = if self.i18n.is_rtl() { egui::panel::Side::Right } else { egui::panel::Side::Left };
+        let panel = egui::SidePanel::new(side, "sidebar")
             .resizable(true)
-            .default_width(200.0)
-            .show_animated(ctx, self.sidebar_open, |ui| {
-                Sidebar::render(
-                    ui,
-                    ctx,
-                    &self.config,
-                    &mut self.current_route,
-                    self.artworks.len(),
-                    &self.resume_content,
-                    &mut self.resume_loading,
-                    &mut self.resume_receiver,
-                    &mut self.settings_open,
-                );
+            .default_width(200.0);
+
+        let mut clicked = None;
+        let add_contents = |ui: &mut egui::Ui| {
+            ui.with_layout(self.i18n.layout(), |ui| {
+                clicked = Sidebar {
+                    config: &self.config,
+                    i18n: &self.i18n,
+                    current_route: self.current_route,
+                    artworks_count: self.artworks.len(),
+                    resume_loading: self.resume_loading,
+                    settings_open: &mut self.settings_open,
+                }
+                .render(ui);
             });
+        };
+
+        // Skip the slide transition when reduced motion is requested
+        if self.motion_mode.is_reduced() {
+            if self.sidebar_open {
+                panel.show(ctx, add_contents);
+            }
+        } else {
+            panel.show_animated(ctx, self.sidebar_open, add_contents);
+        }
+
+        if let Some(route) = clicked {
+            self.navigate(ctx, route);
+        }
     }
 }
 
 impl eframe::App for MeguiApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Font size and zoom shortcuts
+        let default_typography = Typography::from_config(&self.config.typography);
+        self.typography.handle_shortcuts(ctx, &default_typography);
+
         // Apply theme with font sizes
-        self.theme_mode.apply_with_style(ctx);
+        self.theme_mode.apply_with_style(ctx, &self.typography);
+        self.motion_mode.apply(ctx);
+
+        // Global keyboard shortcuts
+        self.handle_shortcuts(ctx);
+
+        // Install custom fonts once loaded
+        self.font_loader.poll(ctx);
 
         // Check for fetch responses
         self.process_fetch_response();
@@ -178,35 +547,42 @@ impl eframe::App for MeguiApp {
 
         // Top bar with menu toggle
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                if ui
-                    .button(if self.sidebar_open {
-                        "☰ Hide Menu"
-                    } else {
-                        "☰ Show Menu"
-                    })
-                    .clicked()
-                {
-                    self.sidebar_open = !self.sidebar_open;
-                }
-                ui.separator();
-
-                // Make site title clickable to go to homepage
-                if ui
-                    .add(egui::Label::new(
-                        egui::RichText::new(&self.config.app.name)
-                            .heading()
-                            .color(ui.visuals().hyperlink_color)
-                    ).sense(egui::Sense::click()))
-                    .on_hover_cursor(egui::CursorIcon::PointingHand)
-                    .clicked()
-                {
-                    self.current_route = Route::Home;
-                    self.current_route.update_browser_url();
-                }
+            a11y::set_role(ui, Role::Banner, None);
+            ui.with_layout(self.i18n.layout(), |ui| {
+                ui.horizontal(|ui| {
+                    if !self.kiosk {
+                        let menu_button = ui.button(if self.sidebar_open {
+                            self.i18n.tr("menu-hide")
+                        } else {
+                            self.i18n.tr("menu-show")
+                        });
+                        a11y::set_label(
+                            &menu_button,
+                            self.i18n.label(if self.sidebar_open { "menu-hide-label" } else { "menu-show-label" }),
+                        );
+                        if menu_button.clicked() {
+                            self.sidebar_open = !self.sidebar_open;
+                        }
+                        ui.separator();
+                    }
 
-                ui.separator();
-                ui.label(self.current_route.title());
+                    // Make site title clickable to go to homepage
+                    if ui
+                        .add(egui::Label::new(
+                            egui::RichText::new(&self.config.app.name)
+                                .heading()
+                                .color(ui.visuals().hyperlink_color)
+                        ).sense(egui::Sense::click()))
+                        .on_hover_cursor(egui::CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        self.current_route = Route::home(&self.config);
+                        self.current_route.update_browser_url(&self.config);
+                    }
+
+                    ui.separator();
+                    ui.label(self.current_route.title(&self.config, &self.i18n));
+                });
             });
         });
 
@@ -215,36 +591,434 @@ impl eframe::App for MeguiApp {
 
         // Render main content based on current route
         egui::CentralPanel::default().show(ctx, |ui| {
-            match self.current_route {
-                Route::Home => HomePage::render(ui, &self.config),
-                Route::Artworks => ArtworksPage::render(
-                    ui,
-                    ctx,
-                    &self.config,
-                    &self.artworks,
-                    &mut self.selected_artworks,
-                    self.loading,
-                    &self.error,
-                ),
-                Route::Resume => ResumePage::render(
-                    ui,
-                    ctx,
-                    &self.config,
-                    &self.resume_content,
-                    &mut self.markdown_cache,
-                ),
-                Route::About => AboutPage::render(ui, &self.config),
-            }
+            a11y::set_role(ui, Role::Main, Some(self.current_route.label(&self.config, &self.i18n)));
+            ui.with_layout(self.i18n.layout(), |ui| {
+                match self.current_route {
+                    Route::Page(idx) => MarkdownPage::render(
+                        ui,
+                        ctx,
+                        &self.config,
+                        &self.i18n,
+                        self.fetcher.as_ref(),
+                        &self.config.pages[idx],
+                        &mut self.page_contents[idx],
+                        &mut self.markdown_cache,
+                    ),
+                    Route::Artworks => ArtworksPage::render(
+                        ui,
+                        ctx,
+                        &self.config,
+                        &self.i18n,
+                        &self.artworks,
+                        &mut self.selected_artworks,
+                        &mut self.search_query,
+                        self.loading,
+                        &self.error,
+                    ),
+                    Route::Resume => {
+                        let previous_section = self.resume_navigation.active.clone();
+                        ResumePage::render(
+                            ui,
+                            ctx,
+                            &self.config,
+                            &self.i18n,
+                            &self.resume_content,
+                            &mut self.markdown_cache,
+                            &mut self.resume_layout,
+                            &mut self.resume_navigation,
+                            &mut self.resume_search,
+                            &mut self.resume_export,
+                        );
+
+                        // Keep `#/resume/<section>` in sync while scrolling
+                        if self.resume_navigation.active != previous_section {
+                            Route::Resume
+                                .update_browser_url_with_section(&self.config, self.resume_navigation.active.as_deref());
+                        }
+                    }
+                    Route::Blog => {
+                        let Some(blog_config) = &self.config.blog else {
+                            return;
+                        };
+                        let previous_post = self.blog.selected.clone();
+                        BlogPage::render(
+                            ui,
+                            ctx,
+                            &self.config,
+                            &self.i18n,
+                            self.fetcher.as_ref(),
+                            blog_config,
+                            &mut self.blog,
+                            &mut self.markdown_cache,
+                        );
+
+                        // Keep `#/blog/<post>` in sync with the open post
+                        if self.blog.selected != previous_post {
+                            Route::Blog.update_browser_url_with_section(&self.config, self.blog.selected.as_deref());
+                        }
+                    }
+                    Route::Links => LinksPage::render(ui, &self.i18n, &self.config.links, &self.link_codes),
+                }
+            });
         });
 
+        if self.current_route != Route::Artworks {
+            self.handle_content_links(ctx);
+        }
+
         // Artwork detail modals (can have multiple open at once)
-        ArtworksPage::render_artwork_modals(ctx, &self.config, &mut self.selected_artworks);
+        ArtworksPage::render_artwork_modals(ctx, &self.config, &self.i18n, &mut self.selected_artworks);
 
         // Settings modal
-        SettingsModal::render(ctx, &self.config, &mut self.theme_mode, &mut self.settings_open);
+        let mut locale = self.locale.clone();
+        SettingsModal::render(
+            ctx,
+            &self.config,
+            &self.i18n,
+            &mut locale,
+            &mut self.theme_mode,
+            &mut self.motion_mode,
+            &mut self.typography,
+            &mut self.settings_open,
+        );
+        if locale != self.locale {
+            self.set_locale(locale);
+        }
+
+        // Command palette
+        if self.command_palette.open {
+            let entries = self.palette_entries();
+            if let Some(command) = self.command_palette.render(ctx, &self.i18n, &entries) {
+                self.run_command(ctx, command);
+            }
+        }
+
+        // Keyboard shortcuts cheat-sheet
+        ShortcutsOverlay::render(ctx, &self.i18n, &self.keymap, &mut self.shortcuts_open);
+
+        // Labels hold right-to-left text in display order; copy it in reading order
+        let rtl = self.i18n.is_rtl();
+        ctx.output_mut(|output| {
+            for command in &mut output.commands {
+                if let egui::OutputCommand::CopyText(text) = command {
+                    if let Cow::Owned(logical) = bidi::logical(text, rtl) {
+                        *text = logical;
+                    }
+                }
+            }
+        });
 
         // Sync hframe (required for iframe rendering on web)
         #[cfg(target_arch = "wasm32")]
         hframe::sync(ctx);
     }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, Typography::STORAGE_KEY, &self.typography);
+        eframe::set_value(storage, MotionMode::STORAGE_KEY, &self.motion_mode);
+        eframe::set_value(storage, I18n::STORAGE_KEY, &self.locale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::FixtureFetch;
+    use egui::accesskit;
+    use egui_kittest::kittest::{NodeT, Queryable};
+
+    type Harness = egui_kittest::Harness<'static, MeguiApp>;
+
+    const ARTWORKS: &str = r#"[{"type": "directory", "name": ".", "contents": [
+        {"name": "alpha", "info": "First light"},
+        {"name": "beta"}
+    ]}]"#;
+
+    const RESUME: &str = r#"<h1>Ada Lovelace</h1><script>alert(1)</script><p>Mathematician</p>
+        <h2>Experience</h2><ul><li>Notes on the <a href="javascript:alert(1)">Analytical Engine</a></li></ul>"#;
+
+    /// Artworks and resume answered from memory
+    fn fixtures() -> FixtureFetch {
+        let config = Config::default();
+        FixtureFetch::new()
+            .with(&config.app.artworks, ARTWORKS)
+            .with(&config.app.resume, RESUME)
+    }
+
+    /// The app in English, fetching from `fixtures`
+    fn harness_with(fixtures: FixtureFetch) -> Harness {
+        let mut app = MeguiApp::with_fetcher(Arc::new(fixtures));
+        app.set_locale("en".to_string());
+        Harness::new_eframe(|_| app)
+    }
+
+    fn harness() -> Harness {
+        harness_with(fixtures())
+    }
+
+    /// Whether a widget with this label or text is on screen
+    fn has(harness: &Harness, text: &str) -> bool {
+        harness.query_all_by_label(text).next().is_some()
+    }
+
+    /// Click the clickable widget with this label or text, then settle
+    fn click(harness: &mut Harness, text: &str) {
+        let Some(widget) = harness
+            .query_all_by_label(text)
+            .find(|node| node.accesskit_node().data().supports_action(accesskit::Action::Click))
+        else {
+            panic!("No widget labelled {:?} in {:#?}", text, harness.root());
+        };
+        widget.click_accesskit();
+        harness.run();
+    }
+
+    /// Press a key with modifiers, then settle
+    fn press(harness: &mut Harness, key: egui::Key, modifiers: egui::Modifiers) {
+        harness.key_press_modifiers(modifiers, key);
+        harness.run();
+    }
+
+    /// Snapshot `<name>_light` and `<name>_dark`, with the menu hidden so the
+    /// copyright year doesn't date them
+    fn snapshot_in_both_themes(harness: &mut Harness, name: &str) {
+        harness.state_mut().sidebar_open = false;
+        for (theme, suffix) in [(ThemeMode::Light, "light"), (ThemeMode::Dark, "dark")] {
+            harness.state_mut().theme_mode = theme;
+            // The loading spinner keeps repainting, so don't wait for it to settle
+            harness.run_ok();
+            harness.snapshot(format!("{}_{}", name, suffix));
+        }
+    }
+
+    fn page(harness: &Harness, slug: &str) -> Route {
+        Route::from_slug(slug, &harness.state().config).expect("Page is in config.toml")
+    }
+
+    #[test]
+    fn sidebar_reaches_every_route() {
+        let mut harness = harness();
+        for route in Route::all(&harness.state().config) {
+            let title = route.title(&harness.state().config, &harness.state().i18n).to_string();
+            click(&mut harness, &title);
+            assert_eq!(harness.state().current_route, route, "clicking {:?}", title);
+        }
+    }
+
+    #[test]
+    fn artwork_windows_open_and_close() {
+        let mut harness = harness();
+        click(&mut harness, "Artworks");
+        assert!(has(&harness, "First light"));
+
+        click(&mut harness, "alpha");
+        click(&mut harness, "beta");
+        click(&mut harness, "alpha");
+        let open: Vec<&str> = harness.state().selected_artworks.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(open, ["alpha", "beta"]);
+
+        press(&mut harness, egui::Key::Escape, egui::Modifiers::NONE);
+        assert_eq!(harness.state().selected_artworks.len(), 1);
+        press(&mut harness, egui::Key::Escape, egui::Modifiers::NONE);
+        assert!(harness.state().selected_artworks.is_empty());
+    }
+
+    #[test]
+    fn artwork_windows_pop_out_and_back() {
+        let mut harness = harness();
+        click(&mut harness, "Artworks");
+        click(&mut harness, "alpha");
+        click(&mut harness, "Show in its own window");
+        assert!(ArtworksPage::is_popped_out(&harness.ctx, "alpha"));
+
+        // Without native windows to open, it stays inside the main one
+        assert!(has(&harness, "Show inside the main window"), "{:#?}", harness.root());
+        click(&mut harness, "Show inside the main window");
+        assert!(!ArtworksPage::is_popped_out(&harness.ctx, "alpha"));
+        assert_eq!(harness.state().selected_artworks.len(), 1);
+    }
+
+    #[test]
+    fn closed_artwork_windows_reopen_inside() {
+        let mut harness = harness();
+        click(&mut harness, "Artworks");
+        click(&mut harness, "alpha");
+        click(&mut harness, "Show in its own window");
+        assert!(ArtworksPage::is_popped_out(&harness.ctx, "alpha"));
+
+        press(&mut harness, egui::Key::Escape, egui::Modifiers::NONE);
+        assert!(harness.state().selected_artworks.is_empty());
+        assert!(!ArtworksPage::is_popped_out(&harness.ctx, "alpha"));
+    }
+
+    #[test]
+    fn settings_switch_the_theme() {
+        let mut harness = harness();
+        click(&mut harness, "Settings");
+        assert!(harness.state().settings_open);
+
+        click(&mut harness, "Dark");
+        assert_eq!(harness.state().theme_mode, ThemeMode::Dark);
+        assert!(harness.ctx.style().visuals.dark_mode);
+
+        click(&mut harness, "Light");
+        assert_eq!(harness.state().theme_mode, ThemeMode::Light);
+        assert!(!harness.ctx.style().visuals.dark_mode);
+    }
+
+    #[test]
+    fn failed_fetches_show_an_error() {
+        let artworks = Config::default().app.artworks;
+        let mut harness = harness_with(fixtures().with_error(&artworks, "connection refused"));
+        click(&mut harness, "Artworks");
+
+        assert!(!harness.state().loading);
+        assert!(has(&harness, "Error: Fetch error: connection refused"), "{:#?}", harness.root());
+    }
+
+    #[test]
+    fn resume_is_fetched_cleaned_and_outlined() {
+        let mut harness = harness();
+        click(&mut harness, "Resume");
+
+        assert_eq!(harness.state().current_route, Route::Resume);
+        assert!(has(&harness, "Contents"));
+        assert!(has(&harness, "Experience"), "{:#?}", harness.root());
+        let markdown = match &harness.state().resume_content {
+            Some(ResumeDocument::Markdown(markdown)) => markdown,
+            other => panic!("Expected a markdown resume, got {:?}", other.is_some()),
+        };
+        assert!(!markdown.contains("alert"));
+    }
+
+    #[test]
+    fn resume_search_finds_matches_outside_the_view() {
+        let filler = "<p>Filler paragraph.</p>".repeat(40);
+        let resume = format!(
+            "<h1>Ada Lovelace</h1><p>Analytical engine</p>{0}<h2>Later</h2>{0}<p>The <strong>Engine</strong> and an engine</p>",
+            filler
+        );
+        let mut harness = harness_with(fixtures().with(&Config::default().app.resume, resume));
+        click(&mut harness, "Resume");
+        harness.state_mut().resume_search.query = "engine".to_string();
+        harness.run();
+        assert!(has(&harness, "1 of 3"), "{:#?}", harness.root());
+
+        click(&mut harness, "Previous match");
+        assert!(has(&harness, "3 of 3"), "{:#?}", harness.root());
+        assert_eq!(harness.state().resume_navigation.active.as_deref(), Some("later"));
+    }
+
+    #[test]
+    fn resume_search_finds_right_to_left_text() {
+        let resume = "<h1>Ada Lovelace</h1><p>שלום עולם</p><p>ברוכים הבאים לעולם</p><p>مرحبا بالعالم</p>";
+        let mut harness = harness_with(fixtures().with(&Config::default().app.resume, resume));
+        click(&mut harness, "Resume");
+
+        // Typed in reading order, though the text is shown in display order
+        harness.state_mut().resume_search.query = "עולם".to_string();
+        harness.run();
+        assert!(has(&harness, "1 of 2"), "{:#?}", harness.root());
+
+        harness.state_mut().resume_search.query = "العالم".to_string();
+        harness.run();
+        assert!(has(&harness, "1 of 1"), "{:#?}", harness.root());
+    }
+
+    #[test]
+    fn reloading_data_fetches_the_open_resume_and_pages_again() {
+        // A page fetched from a URL rather than written in the config
+        let url = "https://example.com/about.md";
+        let mut app = MeguiApp::with_fetcher(Arc::new(fixtures().with(url, "# About me")));
+        app.set_locale("en".to_string());
+        let about = app.config.pages.iter().position(|page| page.slug == "about").expect("Page is in config.toml");
+        app.config.pages[about].markdown = None;
+        app.config.pages[about].url = Some(url.to_string());
+        let mut harness = Harness::new_eframe(|_| app);
+
+        click(&mut harness, "About");
+        assert!(matches!(harness.state().page_contents[about], PageContent::Loaded(_)));
+        click(&mut harness, "Resume");
+
+        let ctx = harness.ctx.clone();
+        harness.state_mut().run_command(&ctx, Command::ReloadData);
+        assert!(matches!(harness.state().page_contents[about], PageContent::NotLoaded));
+        harness.run();
+
+        assert_eq!(harness.state().current_route, Route::Resume);
+        assert!(harness.state().resume_content.is_some());
+        assert!(!has(&harness, "Loading resume..."), "{:#?}", harness.root());
+
+        click(&mut harness, "About");
+        assert!(matches!(harness.state().page_contents[about], PageContent::Loaded(_)));
+    }
+
+    #[test]
+    fn kiosk_starts_on_the_route_without_the_menu() {
+        let options = StartOptions {
+            route: Some("#/resume/experience".to_string()),
+            kiosk: true,
+            ..Default::default()
+        };
+        let mut app = MeguiApp::with_options(options, Arc::new(fixtures()));
+        app.set_locale("en".to_string());
+        let mut harness = Harness::new_eframe(|_| app);
+
+        assert_eq!(harness.state().current_route, Route::Resume);
+        assert_eq!(harness.state().resume_navigation.scroll_to.as_deref(), Some("experience"));
+        assert!(!has(&harness, "Show menu") && !has(&harness, "Settings"));
+        press(&mut harness, egui::Key::Comma, egui::Modifiers::NONE);
+        assert!(!harness.state().settings_open);
+    }
+
+    #[test]
+    fn snapshot_markdown_pages() {
+        let mut harness = harness();
+        for slug in ["home", "about"] {
+            harness.state_mut().current_route = page(&harness, slug);
+            snapshot_in_both_themes(&mut harness, slug);
+        }
+    }
+
+    #[test]
+    fn snapshot_artworks_states() {
+        // One harness per state, reported together
+        let mut results = egui_kittest::SnapshotResults::new();
+
+        // Still waiting for the index
+        let mut harness = harness();
+        harness.state_mut().current_route = Route::Artworks;
+        harness.state_mut().artworks.clear();
+        harness.state_mut().loading = true;
+        harness.state_mut().fetch_receiver = Some(std::sync::mpsc::channel().1);
+        snapshot_in_both_themes(&mut harness, "artworks_loading");
+        results.extend_harness(&mut harness);
+
+        let artworks = Config::default().app.artworks;
+        let mut harness = harness_with(fixtures().with_error(&artworks, "connection refused"));
+        harness.state_mut().current_route = Route::Artworks;
+        snapshot_in_both_themes(&mut harness, "artworks_error");
+        results.extend_harness(&mut harness);
+
+        let mut harness = self::harness();
+        harness.state_mut().current_route = Route::Artworks;
+        snapshot_in_both_themes(&mut harness, "artworks");
+        results.extend_harness(&mut harness);
+    }
+
+    #[test]
+    fn snapshot_resume() {
+        // Kept apart from `RESUME` so the snapshot doesn't change with that fixture
+        let html = "<h1>Ada Lovelace</h1><p>Mathematician</p><h2>Experience</h2><ul><li>Analytical Engine notes</li></ul>";
+        let mut harness = harness_with(fixtures().with(&Config::default().app.resume, html));
+        click(&mut harness, "Resume");
+        snapshot_in_both_themes(&mut harness, "resume");
+    }
+
+    #[test]
+    fn snapshot_settings() {
+        let mut harness = harness();
+        harness.state_mut().settings_open = true;
+        snapshot_in_both_themes(&mut harness, "settings");
+    }
 }
@@ -5,6 +5,23 @@ pub struct Artwork {
     pub name: String,
     #[serde(default)]
     pub info: Option<String>,
+    /// Preview image, relative to the artwork's folder or a full URL
+    #[serde(default)]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub thumbnail: Option<String>,
+}
+
+impl Artwork {
+    /// Where the thumbnail is, for an index at `index_url`
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn thumbnail_url(&self, index_url: &str) -> Option<String> {
+        let thumbnail = self.thumbnail.as_ref()?;
+        if thumbnail.contains("://") {
+            return Some(thumbnail.clone());
+        }
+        let artworks_base = index_url.trim_end_matches("/index.json");
+        Some(format!("{}/{}/{}", artworks_base, self.name, thumbnail))
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -15,3 +32,17 @@ pub struct ArtworksResponse {
     name: String,
     pub contents: Vec<Artwork>,
 }
+
+/// Why an artworks index has nothing to show
+#[derive(Debug)]
+pub enum IndexError {
+    Invalid(serde_json::Error),
+    Empty,
+}
+
+/// Artworks in an index as served at `app.artworks`: a JSON list whose first
+/// entry is the artworks folder
+pub fn parse_index(text: &str) -> Result<Vec<Artwork>, IndexError> {
+    let data: Vec<ArtworksResponse> = serde_json::from_str(text).map_err(IndexError::Invalid)?;
+    data.into_iter().next().map(|folder| folder.contents).ok_or(IndexError::Empty)
+}
@@ -0,0 +1,883 @@
+//! Right-to-left text for egui, which lays glyphs out left to right as given
+//! and does no contextual shaping of its own. Text is shaped and broken into
+//! lines in reading order, then each line is put in display order.
+//!
+//! egui 0.33 has no bidi algorithm, shaper or font fallback by script, so
+//! nothing here duplicates it: `unicode-bidi` supplies the reordering, and the
+//! rest is line breaking done before reordering (which egui's own wrapping
+//! can't do), Arabic joining forms, and undoing both for search and copying.
+
+use eframe::egui;
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::borrow::Cow;
+use std::ops::Range;
+use std::sync::Arc;
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
+
+use crate::components::a11y;
+use crate::markdown;
+
+/// Languages written right to left, by ISO 639 code
+const RTL_LANGUAGES: [&str; 10] = ["ar", "ckb", "dv", "fa", "he", "iw", "ps", "sd", "ug", "ur"];
+
+/// Extensions `CommonMarkViewer` parses with, so tables and the like survive
+const VIEWER_OPTIONS: Options = Options::ENABLE_TABLES
+    .union(Options::ENABLE_TASKLISTS)
+    .union(Options::ENABLE_STRIKETHROUGH)
+    .union(Options::ENABLE_FOOTNOTES)
+    .union(Options::ENABLE_DEFINITION_LIST);
+
+/// How an Arabic letter connects to its neighbours
+#[derive(Clone, Copy, PartialEq)]
+enum Joining {
+    /// Connects on both sides
+    Dual,
+    /// Connects only to the letter before it
+    Right,
+    /// Never connects
+    None,
+}
+
+impl Joining {
+    /// How many presentation forms a letter that joins this way has
+    fn forms(self) -> u32 {
+        match self {
+            Joining::Dual => 4,
+            Joining::Right => 2,
+            Joining::None => 1,
+        }
+    }
+}
+
+/// Letters with presentation forms: the isolated form comes first, then
+/// final, initial and medial as far as the letter has them
+const LETTERS: [(char, Joining, u32); 42] = [
+    ('\u{0621}', Joining::None, 0xFE80),
+    ('\u{0622}', Joining::Right, 0xFE81),
+    ('\u{0623}', Joining::Right, 0xFE83),
+    ('\u{0624}', Joining::Right, 0xFE85),
+    ('\u{0625}', Joining::Right, 0xFE87),
+    ('\u{0626}', Joining::Dual, 0xFE89),
+    ('\u{0627}', Joining::Right, 0xFE8D),
+    ('\u{0628}', Joining::Dual, 0xFE8F),
+    ('\u{0629}', Joining::Right, 0xFE93),
+    ('\u{062A}', Joining::Dual, 0xFE95),
+    ('\u{062B}', Joining::Dual, 0xFE99),
+    ('\u{062C}', Joining::Dual, 0xFE9D),
+    ('\u{062D}', Joining::Dual, 0xFEA1),
+    ('\u{062E}', Joining::Dual, 0xFEA5),
+    ('\u{062F}', Joining::Right, 0xFEA9),
+    ('\u{0630}', Joining::Right, 0xFEAB),
+    ('\u{0631}', Joining::Right, 0xFEAD),
+    ('\u{0632}', Joining::Right, 0xFEAF),
+    ('\u{0633}', Joining::Dual, 0xFEB1),
+    ('\u{0634}', Joining::Dual, 0xFEB5),
+    ('\u{0635}', Joining::Dual, 0xFEB9),
+    ('\u{0636}', Joining::Dual, 0xFEBD),
+    ('\u{0637}', Joining::Dual, 0xFEC1),
+    ('\u{0638}', Joining::Dual, 0xFEC5),
+    ('\u{0639}', Joining::Dual, 0xFEC9),
+    ('\u{063A}', Joining::Dual, 0xFECD),
+    ('\u{0641}', Joining::Dual, 0xFED1),
+    ('\u{0642}', Joining::Dual, 0xFED5),
+    ('\u{0643}', Joining::Dual, 0xFED9),
+    ('\u{0644}', Joining::Dual, 0xFEDD),
+    ('\u{0645}', Joining::Dual, 0xFEE1),
+    ('\u{0646}', Joining::Dual, 0xFEE5),
+    ('\u{0647}', Joining::Dual, 0xFEE9),
+    ('\u{0648}', Joining::Right, 0xFEED),
+    ('\u{0649}', Joining::Right, 0xFEEF),
+    ('\u{064A}', Joining::Dual, 0xFEF1),
+    // Persian and Urdu letters
+    ('\u{067E}', Joining::Dual, 0xFB56),
+    ('\u{0686}', Joining::Dual, 0xFB7A),
+    ('\u{0698}', Joining::Right, 0xFB8A),
+    ('\u{06A9}', Joining::Dual, 0xFB8E),
+    ('\u{06AF}', Joining::Dual, 0xFB92),
+    ('\u{06CC}', Joining::Dual, 0xFBFC),
+];
+
+/// Lam followed by one of these alefs is drawn as a single ligature
+const LAM_ALEF: [(char, u32); 4] = [
+    ('\u{0622}', 0xFEF5),
+    ('\u{0623}', 0xFEF7),
+    ('\u{0625}', 0xFEF9),
+    ('\u{0627}', 0xFEFB),
+];
+
+const LAM: char = '\u{0644}';
+const TATWEEL: char = '\u{0640}';
+
+/// Whether a locale such as "ar" or "he-IL" is written right to left
+pub fn is_rtl_locale(locale: &str) -> bool {
+    let language = locale.split(['-', '_']).next().unwrap_or(locale).to_lowercase();
+    RTL_LANGUAGES.contains(&language.as_str())
+}
+
+/// Whether `text` has any right-to-left letters
+pub fn has_rtl(text: &str) -> bool {
+    text.chars().any(|c| matches!(bidi_class(c), BidiClass::R | BidiClass::AL))
+}
+
+/// `text` shaped and in display order, line by line; unchanged when it has
+/// no right-to-left characters. For short text that isn't wrapped, such as
+/// titles and buttons; paragraphs go through `label` or `wrap`.
+pub fn visual(text: &str) -> Cow<'_, str> {
+    if !has_rtl(text) {
+        return Cow::Borrowed(text);
+    }
+    let lines: Vec<String> = wrap(text, f32::INFINITY, |_| 0.0).into_iter().map(|line| line.text).collect();
+    Cow::Owned(lines.join("\n"))
+}
+
+/// A line of text in display order
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisualLine {
+    pub text: String,
+    /// Whether its paragraph reads right to left, and so aligns to the right
+    pub rtl: bool,
+}
+
+/// `text` broken into lines no wider than `max_width`, as measured by
+/// `width`, with each line then put in display order
+pub fn wrap(text: &str, max_width: f32, width: impl Fn(&str) -> f32) -> Vec<VisualLine> {
+    let spans = [Span {
+        text: text.to_string(),
+        style: (),
+    }];
+    visual_lines(&spans, max_width, &|piece, _| width(piece), None)
+        .into_iter()
+        .map(|(spans, rtl)| VisualLine {
+            text: spans.into_iter().map(|span| span.text).collect(),
+            rtl,
+        })
+        .collect()
+}
+
+/// A label for text that may be right to left: wrapped at the available
+/// width, each line reordered, and aligned to the side it reads from.
+/// Screen readers get the text in reading order.
+pub fn label(ui: &mut egui::Ui, text: impl Into<egui::RichText>) -> egui::Response {
+    let text = text.into();
+    if !has_rtl(text.text()) {
+        return ui.label(text);
+    }
+    let logical = text.text().to_string();
+
+    let job = egui::WidgetText::from(text).into_layout_job(ui.style(), egui::FontSelection::Default, ui.text_valign());
+    let format = job.sections.first().map(|section| section.format.clone()).unwrap_or_default();
+    let lines = wrap(&logical, ui.available_width(), |piece| {
+        let piece = egui::text::LayoutJob::single_section(piece.to_string(), format.clone());
+        ui.fonts_mut(|fonts| fonts.layout_job(piece)).size().x
+    });
+
+    let text: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+    let mut job = egui::text::LayoutJob::single_section(text.join("\n"), format);
+    job.halign = match lines.first().is_some_and(|line| line.rtl) {
+        true => egui::Align::RIGHT,
+        false => egui::Align::LEFT,
+    };
+    let galley = ui.fonts_mut(|fonts| fonts.layout_job(job));
+
+    let response = ui.label(galley);
+    a11y::set_label(&response, &logical);
+    response
+}
+
+/// Text copied from what this module laid out, back in reading order. Each
+/// line is taken as a paragraph reading right to left when `rtl` is set:
+/// letters lose their joining forms and the line is reordered again, which
+/// undoes the first reordering.
+pub fn logical(text: &str, rtl: bool) -> Cow<'_, str> {
+    if !has_rtl(text) {
+        return Cow::Borrowed(text);
+    }
+    let level = if rtl { Level::rtl() } else { Level::ltr() };
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| {
+            let letters: String = unshape(line).into_iter().map(|(c, _)| c).collect();
+            reorder(&letters, Some(level))
+        })
+        .collect();
+    Cow::Owned(lines.join("\n"))
+}
+
+/// Case-insensitive occurrences of `query`, typed in reading order, in text
+/// this module laid out, as char ranges of that text. Letters are compared
+/// without their joining forms, and right-to-left parts of the query are
+/// looked for in display order as well.
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    if !has_rtl(text) {
+        return markdown::find_matches(text, query);
+    }
+    let letters = unshape(text);
+    let plain: String = letters.iter().map(|(c, _)| c).collect();
+
+    let mut forms = vec![query.to_string()];
+    if has_rtl(query) {
+        forms.extend([Level::rtl(), Level::ltr()].map(|level| reorder(query, Some(level))));
+        forms.dedup();
+    }
+
+    let mut ranges: Vec<Range<usize>> = forms
+        .iter()
+        .flat_map(|form| markdown::find_matches(&plain, form))
+        .map(|range| letters[range.start].1..letters[range.end - 1].1 + 1)
+        .collect();
+    ranges.sort_by_key(|range| range.start);
+    let mut matches: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        if matches.last().map_or(true, |last| last.end <= range.start) {
+            matches.push(range);
+        }
+    }
+    matches
+}
+
+/// `text` as a single line in display order, with its letters in their
+/// plain forms
+fn reorder(text: &str, level: Option<Level>) -> String {
+    let spans = [Span {
+        text: text.to_string(),
+        style: (),
+    }];
+    let lines = visual_lines(&spans, f32::INFINITY, &|_, _| 0.0, level);
+    let visual: String = lines.into_iter().flat_map(|(spans, _)| spans).map(|span| span.text).collect();
+    unshape(&visual).into_iter().map(|(c, _)| c).collect()
+}
+
+/// How `CommonMarkViewer` measures text, for breaking right-to-left
+/// paragraphs into lines before they are reordered
+pub struct TextMetrics<'a> {
+    /// Width available to a top-level paragraph
+    pub max_width: f32,
+    /// Width taken by each level of list or quote around a paragraph
+    pub indent: f32,
+    /// Width of some text, in the code font when the flag is set
+    pub width: &'a dyn Fn(&str, bool) -> f32,
+}
+
+/// `markdown` as `visual_markdown` has it for the width available in `ui`,
+/// or `None` when it has no right-to-left text. Kept from frame to frame
+/// until the width changes.
+pub fn markdown_in(ui: &egui::Ui, markdown: &str) -> Option<Arc<str>> {
+    if !has_rtl(markdown) {
+        return None;
+    }
+
+    let max_width = ui.available_width();
+    let id = egui::Id::new(("bidi_markdown", markdown));
+    let cached: Option<(f32, Arc<str>)> = ui.data(|d| d.get_temp(id));
+    if let Some((width, visual)) = cached {
+        if width == max_width {
+            return Some(visual);
+        }
+    }
+
+    let body = egui::TextStyle::Body.resolve(ui.style());
+    let code = egui::TextStyle::Monospace.resolve(ui.style());
+    let width = |text: &str, is_code: bool| {
+        let font = if is_code { code.clone() } else { body.clone() };
+        ui.fonts_mut(|fonts| fonts.layout_no_wrap(text.to_string(), font, egui::Color32::PLACEHOLDER))
+            .size()
+            .x
+    };
+    let metrics = TextMetrics {
+        // Some slack, since runs are measured apart from their neighbours
+        max_width: max_width - body.size,
+        // The viewer's bullet and list indentation
+        indent: width("        ", false),
+        width: &width,
+    };
+
+    let visual: Arc<str> = visual_markdown(markdown, &metrics).into();
+    ui.data_mut(|d| d.insert_temp(id, (max_width, visual.clone())));
+    Some(visual)
+}
+
+/// Markdown with each paragraph that has right-to-left text broken into
+/// lines that fit `metrics`, and each line put in display order across its
+/// bold, italic, code and link runs. Lines are joined with hard breaks.
+/// Headings and table cells are reordered without breaking, and code blocks
+/// are left alone.
+pub fn visual_markdown(markdown: &str, metrics: &TextMetrics) -> String {
+    let mut rewrites: Vec<(Range<usize>, String)> = Vec::new();
+    let mut paragraph = Paragraph::default();
+    let mut style = Inline::default();
+    let mut nesting = 0;
+    let mut unbroken = 0;
+    let mut in_code = false;
+
+    for (event, range) in Parser::new_ext(markdown, VIEWER_OPTIONS).into_offset_iter() {
+        match &event {
+            Event::Start(Tag::CodeBlock(_)) => in_code = true,
+            Event::End(TagEnd::CodeBlock) => in_code = false,
+            _ => {}
+        }
+
+        let inline = match &event {
+            Event::Text(_) if in_code => false,
+            Event::Text(text) => {
+                paragraph.push(text, &range, &style, markdown);
+                true
+            }
+            Event::Code(text) => {
+                let code = Inline {
+                    code: true,
+                    ..style.clone()
+                };
+                paragraph.push(text, &range, &code, markdown);
+                true
+            }
+            Event::SoftBreak => {
+                paragraph.push(&" ".into(), &range, &style, markdown);
+                true
+            }
+            Event::HardBreak => {
+                paragraph.push(&"\n".into(), &range, &style, markdown);
+                true
+            }
+            Event::Start(tag) => style.start(tag),
+            Event::End(tag) => style.end(tag),
+            // Kept in the paragraph, but nothing around them can be moved
+            Event::InlineHtml(_) | Event::FootnoteReference(_) | Event::InlineMath(_) => {
+                paragraph.fixed = true;
+                true
+            }
+            _ => false,
+        };
+
+        if inline {
+            if matches!(event, Event::Start(Tag::Image { .. })) {
+                paragraph.fixed = true;
+            }
+            paragraph.extend(&range);
+            continue;
+        }
+
+        // Anything else ends the paragraph
+        let max_width = match unbroken {
+            0 => metrics.max_width - nesting as f32 * metrics.indent,
+            _ => f32::INFINITY,
+        };
+        rewrites.extend(std::mem::take(&mut paragraph).rewrite(max_width, metrics));
+
+        match event {
+            Event::Start(Tag::List(_) | Tag::BlockQuote(_)) => nesting += 1,
+            Event::End(TagEnd::List(_) | TagEnd::BlockQuote(_)) => nesting -= 1,
+            Event::Start(Tag::Heading { .. } | Tag::TableCell) => unbroken += 1,
+            Event::End(TagEnd::Heading(_) | TagEnd::TableCell) => unbroken -= 1,
+            _ => {}
+        }
+    }
+    rewrites.extend(paragraph.rewrite(metrics.max_width, metrics));
+
+    let mut out = String::with_capacity(markdown.len());
+    let mut copied = 0;
+    for (range, text) in rewrites {
+        out.push_str(&markdown[copied..range.start]);
+        out.push_str(&text);
+        copied = range.end;
+    }
+    out.push_str(&markdown[copied..]);
+    out
+}
+
+/// Formatting of a run of markdown text
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Inline {
+    strong: bool,
+    emphasis: bool,
+    strikethrough: bool,
+    code: bool,
+    /// Destination and title
+    link: Option<(String, String)>,
+}
+
+impl Inline {
+    /// Apply an inline tag; `false` for tags that start a block
+    fn start(&mut self, tag: &Tag) -> bool {
+        match tag {
+            Tag::Emphasis => self.emphasis = true,
+            Tag::Strong => self.strong = true,
+            Tag::Strikethrough => self.strikethrough = true,
+            Tag::Link { dest_url, title, .. } => self.link = Some((dest_url.to_string(), title.to_string())),
+            Tag::Image { .. } => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// Close an inline tag; `false` for tags that end a block
+    fn end(&mut self, tag: &TagEnd) -> bool {
+        match tag {
+            TagEnd::Emphasis => self.emphasis = false,
+            TagEnd::Strong => self.strong = false,
+            TagEnd::Strikethrough => self.strikethrough = false,
+            TagEnd::Link => self.link = None,
+            TagEnd::Image => {}
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Text in one style
+#[derive(Debug, Clone, PartialEq)]
+struct Span<S> {
+    text: String,
+    style: S,
+}
+
+/// Inline content of a paragraph, heading or table cell, as it is parsed
+#[derive(Default)]
+struct Paragraph {
+    /// Source of the inline content
+    range: Option<Range<usize>>,
+    spans: Vec<Span<Inline>>,
+    /// Text that matches its source exactly, for when the paragraph can't be
+    /// rewritten as a whole
+    runs: Vec<(Range<usize>, String)>,
+    /// Set by images, HTML and the like, which can't be written back
+    fixed: bool,
+}
+
+impl Paragraph {
+    fn push(&mut self, text: &CowStr, range: &Range<usize>, style: &Inline, markdown: &str) {
+        if &markdown[range.clone()] == text.as_ref() {
+            self.runs.push((range.clone(), text.to_string()));
+        }
+        self.spans.push(Span {
+            text: text.to_string(),
+            style: style.clone(),
+        });
+    }
+
+    fn extend(&mut self, range: &Range<usize>) {
+        self.range = Some(match self.range.take() {
+            Some(current) => current.start.min(range.start)..current.end.max(range.end),
+            None => range.clone(),
+        });
+    }
+
+    /// Replacements for the paragraph's source, if it has right-to-left text
+    fn rewrite(self, max_width: f32, metrics: &TextMetrics) -> Vec<(Range<usize>, String)> {
+        let Some(range) = self.range else {
+            return Vec::new();
+        };
+        if !self.spans.iter().any(|span| has_rtl(&span.text)) {
+            return Vec::new();
+        }
+
+        if self.fixed {
+            // Each run reordered where it was written
+            return self
+                .runs
+                .into_iter()
+                .filter_map(|(range, text)| match visual(&text) {
+                    Cow::Owned(visual) => Some((range, escape(&visual))),
+                    Cow::Borrowed(_) => None,
+                })
+                .collect();
+        }
+
+        let width = |text: &str, style: &Inline| (metrics.width)(text, style.code);
+        let lines: Vec<String> = visual_lines(&self.spans, max_width, &width, None)
+            .into_iter()
+            .map(|(spans, _)| spans.iter().map(span_markdown).collect())
+            .collect();
+        vec![(range, lines.join("\\\n"))]
+    }
+}
+
+/// Break styled text into lines no wider than `max_width` and put each line
+/// in display order, keeping neighbouring text of the same style together.
+/// Paragraphs take their direction from `level`, or else from their first
+/// letter. Each line comes with whether its paragraph reads right to left.
+fn visual_lines<S: Clone + PartialEq>(
+    spans: &[Span<S>],
+    max_width: f32,
+    width: &dyn Fn(&str, &S) -> f32,
+    level: Option<Level>,
+) -> Vec<(Vec<Span<S>>, bool)> {
+    // Shaped in reading order, with the span each byte came from
+    let mut text = String::new();
+    let mut span_at = Vec::new();
+    for (idx, span) in spans.iter().enumerate() {
+        let shaped = shape_arabic(&span.text);
+        span_at.extend(std::iter::repeat(idx).take(shaped.len()));
+        text.push_str(&shaped);
+    }
+
+    let info = BidiInfo::new(&text, level);
+    let width = |piece: &str, idx: usize| width(piece, &spans[idx].style);
+    let mut lines = Vec::new();
+
+    for line in break_lines(&text, &span_at, max_width, &width) {
+        let Some(paragraph) = info
+            .paragraphs
+            .iter()
+            .find(|p| p.range.contains(&line.start) || p.range.end == line.start)
+        else {
+            continue;
+        };
+
+        let mut visual: Vec<Span<S>> = Vec::new();
+        if !line.is_empty() {
+            let (levels, runs) = info.visual_runs(paragraph, line.clone());
+            for run in runs {
+                let rtl = levels[run.start].is_rtl();
+                let mut chars: Vec<(usize, char)> = text[run.clone()].char_indices().collect();
+                if rtl {
+                    chars.reverse();
+                }
+                for (offset, c) in chars {
+                    // Brackets inside right-to-left runs face the other way
+                    let c = if rtl { mirror(c).unwrap_or(c) } else { c };
+                    let style = &spans[span_at[run.start + offset]].style;
+                    match visual.last_mut() {
+                        Some(last) if last.style == *style => last.text.push(c),
+                        _ => visual.push(Span {
+                            text: c.to_string(),
+                            style: style.clone(),
+                        }),
+                    }
+                }
+            }
+        }
+        lines.push((visual, paragraph.level.is_rtl()));
+    }
+    lines
+}
+
+/// Byte ranges of the lines of `text`, breaking after spaces so lines stay
+/// within `max_width` where they can, and at every newline. Spaces at the
+/// end of a line are left off.
+fn break_lines(
+    text: &str,
+    span_at: &[usize],
+    max_width: f32,
+    width: &dyn Fn(&str, usize) -> f32,
+) -> Vec<Range<usize>> {
+    // Measured in pieces of one span each
+    let measure = |range: Range<usize>| {
+        let mut total = 0.0;
+        let mut start = range.start;
+        for (offset, _) in text[range.clone()].char_indices().skip(1) {
+            let idx = range.start + offset;
+            if span_at[idx] != span_at[start] {
+                total += width(&text[start..idx], span_at[start]);
+                start = idx;
+            }
+        }
+        if start < range.end {
+            total += width(&text[start..range.end], span_at[start]);
+        }
+        total
+    };
+    let trimmed = |range: Range<usize>| range.start..range.start + text[range].trim_end().len();
+
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut line_width = 0.0;
+    let mut word_start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((idx, c)) = chars.next() {
+        let next = chars.peek().map(|(_, next)| *next);
+        let ends_word = c == '\n' || next.map_or(true, |next| c.is_whitespace() && !next.is_whitespace());
+        if !ends_word {
+            continue;
+        }
+
+        let word_end = if c == '\n' { idx } else { idx + c.len_utf8() };
+        let word = word_start..word_end;
+        if line_width > 0.0 && line_width + measure(trimmed(word.clone())) > max_width {
+            lines.push(trimmed(line_start..word.start));
+            line_start = word.start;
+            line_width = 0.0;
+        }
+        line_width += measure(word);
+        word_start = idx + c.len_utf8();
+
+        if c == '\n' {
+            lines.push(trimmed(line_start..idx));
+            line_start = word_start;
+            line_width = 0.0;
+        }
+    }
+    lines.push(trimmed(line_start..text.len()));
+    lines
+}
+
+/// A run of text written back as markdown in its style
+fn span_markdown(span: &Span<Inline>) -> String {
+    let text = &span.text;
+    let style = &span.style;
+
+    let core = text.trim();
+    if core.is_empty() {
+        return text.clone();
+    }
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+
+    let mut inner = if style.code {
+        let fence = "`".repeat(longest_run(core, '`') + 1);
+        let pad = if core.starts_with('`') || core.ends_with('`') { " " } else { "" };
+        format!("{fence}{pad}{core}{pad}{fence}")
+    } else {
+        escape(core)
+    };
+    if style.emphasis {
+        inner = format!("*{}*", inner);
+    }
+    if style.strong {
+        inner = format!("**{}**", inner);
+    }
+    if style.strikethrough {
+        inner = format!("~~{}~~", inner);
+    }
+    if let Some((url, title)) = &style.link {
+        inner = match title.is_empty() {
+            true => format!("[{}](<{}>)", inner, url),
+            false => format!("[{}](<{}> \"{}\")", inner, url, title.replace('"', "\\\"")),
+        };
+    }
+    format!("{}{}{}", leading, inner, trailing)
+}
+
+/// Text with markdown punctuation escaped, so it reads back as written
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_punctuation() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Length of the longest run of `c` in `text`
+fn longest_run(text: &str, c: char) -> usize {
+    text.split(|other| other != c).map(str::len).max().unwrap_or(0)
+}
+
+fn mirror(c: char) -> Option<char> {
+    Some(match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        _ => return None,
+    })
+}
+
+/// Replace Arabic letters with the presentation form for their position in
+/// the word, and lam-alef pairs with their ligature
+fn shape_arabic(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut shaped = String::with_capacity(text.len());
+    let mut skip_alef = false;
+
+    for (idx, &c) in chars.iter().enumerate() {
+        if skip_alef && !is_transparent(c) {
+            skip_alef = false;
+            continue;
+        }
+        let Some((joining, first_form)) = letter(c) else {
+            shaped.push(c);
+            continue;
+        };
+
+        let joins_before = neighbour(&chars[..idx], true).is_some_and(joins_forward);
+        let next = neighbour(&chars[idx + 1..], false);
+
+        if c == LAM {
+            if let Some((_, ligature)) = next.and_then(|n| LAM_ALEF.iter().find(|(alef, _)| *alef == n)) {
+                shaped.extend(char::from_u32(ligature + u32::from(joins_before)));
+                skip_alef = true;
+                continue;
+            }
+        }
+
+        let joins_after = joining == Joining::Dual && next.is_some_and(joins_backward);
+        let offset = match (joining, joins_before, joins_after) {
+            (Joining::None, _, _) | (_, false, false) => 0,
+            (_, true, false) => 1,
+            (_, false, true) => 2,
+            (_, true, true) => 3,
+        };
+        shaped.extend(char::from_u32(first_form + offset));
+    }
+
+    shaped
+}
+
+/// The letters behind shaped text, each with the index of the char it came
+/// from. A lam-alef ligature gives both letters, in display order.
+fn unshape(text: &str) -> Vec<(char, usize)> {
+    let mut letters = Vec::with_capacity(text.len());
+    for (idx, c) in text.chars().enumerate() {
+        let code = u32::from(c);
+        if let Some((alef, _)) = LAM_ALEF.iter().find(|(_, ligature)| (*ligature..ligature + 2).contains(&code)) {
+            letters.extend([(*alef, idx), (LAM, idx)]);
+            continue;
+        }
+        let letter = LETTERS
+            .iter()
+            .find(|(_, joining, form)| (*form..form + joining.forms()).contains(&code))
+            .map_or(c, |(letter, _, _)| *letter);
+        letters.push((letter, idx));
+    }
+    letters
+}
+
+fn letter(c: char) -> Option<(Joining, u32)> {
+    LETTERS
+        .iter()
+        .find(|(letter, _, _)| *letter == c)
+        .map(|(_, joining, form)| (*joining, *form))
+}
+
+/// Nearest character before (`backwards`) or after, skipping vowel marks
+fn neighbour(chars: &[char], backwards: bool) -> Option<char> {
+    let mut iter: Box<dyn Iterator<Item = &char>> = match backwards {
+        true => Box::new(chars.iter().rev()),
+        false => Box::new(chars.iter()),
+    };
+    iter.find(|c| !is_transparent(**c)).copied()
+}
+
+/// Whether `c` connects to the letter after it
+fn joins_forward(c: char) -> bool {
+    c == TATWEEL || letter(c).is_some_and(|(joining, _)| joining == Joining::Dual)
+}
+
+/// Whether `c` connects to the letter before it
+fn joins_backward(c: char) -> bool {
+    c == TATWEEL || letter(c).is_some_and(|(joining, _)| joining != Joining::None)
+}
+
+/// Vowel marks, which sit on a letter without breaking its connections
+fn is_transparent(c: char) -> bool {
+    matches!(c, '\u{064B}'..='\u{065F}' | '\u{0670}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One unit per character, so tests can count where lines break
+    fn chars(text: &str) -> f32 {
+        text.chars().count() as f32
+    }
+
+    fn metrics(max_width: f32) -> TextMetrics<'static> {
+        TextMetrics {
+            max_width,
+            indent: 2.0,
+            width: &|text, _| chars(text),
+        }
+    }
+
+    #[test]
+    fn left_to_right_text_is_untouched() {
+        assert!(matches!(visual("Page 2 of 5"), Cow::Borrowed(_)));
+        let markdown = "# Hello\n\n[link](https://a.b) and *more* text";
+        assert_eq!(visual_markdown(markdown, &metrics(4.0)), markdown);
+        assert!(is_rtl_locale("ar-EG"));
+        assert!(is_rtl_locale("he"));
+        assert!(!is_rtl_locale("fr"));
+    }
+
+    #[test]
+    fn hebrew_is_reordered_and_brackets_mirrored() {
+        assert_eq!(visual("שלום"), "םולש");
+        assert_eq!(visual("עמוד 2 (מתוך 5)"), "(5 ךותמ) 2 דומע");
+        assert_eq!(visual("Hi שלום"), "Hi םולש");
+    }
+
+    #[test]
+    fn arabic_letters_take_their_joining_forms() {
+        // Beh-alef-beh: initial beh, final alef, then an isolated beh since alef
+        // doesn't connect forward; displayed right to left
+        assert_eq!(shape_arabic("\u{0628}\u{0627}\u{0628}"), "\u{FE91}\u{FE8E}\u{FE8F}");
+        // Lam-alef ligature
+        assert_eq!(shape_arabic("\u{0644}\u{0627}"), "\u{FEFB}");
+        // Vowel marks don't break the connection
+        assert_eq!(shape_arabic("\u{0628}\u{064E}\u{0628}"), "\u{FE91}\u{064E}\u{FE90}");
+    }
+
+    #[test]
+    fn lines_are_broken_before_they_are_reordered() {
+        // The words read first go on the top line, and each line is reversed on its own
+        let lines = wrap("אחד שתיים שלוש ארבע", 10.0, chars);
+        let text: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(text, ["םייתש דחא", "עברא שולש"]);
+        assert!(lines.iter().all(|line| line.rtl));
+
+        let lines = wrap("Hello עולם", 5.0, chars);
+        let expected = VisualLine {
+            text: "Hello".to_string(),
+            rtl: false,
+        };
+        assert_eq!(lines[0], expected);
+        assert_eq!(lines[1].text, "םלוע");
+    }
+
+    #[test]
+    fn markdown_runs_are_ordered_across_markup() {
+        // Bold is read first, so it ends up on the right, and the link keeps its target
+        assert_eq!(
+            visual_markdown("**שלום** [עולם](https://example.com)", &metrics(80.0)),
+            "[םלוע](<https://example.com>) **םולש**"
+        );
+        // Long paragraphs become hard-broken lines, the top one read first
+        assert_eq!(
+            visual_markdown("אחד שתיים *שלוש* ארבע", &metrics(10.0)),
+            "םייתש דחא\\\nעברא *שולש*"
+        );
+    }
+
+    #[test]
+    fn copied_text_comes_back_in_reading_order() {
+        for text in ["עמוד 2 (מתוך 5)", "\u{0628}\u{0627}\u{0628} \u{0644}\u{0627}", "Hello"] {
+            assert_eq!(logical(&visual(text), true), text);
+        }
+        let lines = wrap("אחד שתיים שלוש ארבע", 10.0, chars);
+        let text: Vec<&str> = lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(logical(&text.join("\n"), true), "אחד שתיים\nשלוש ארבע");
+    }
+
+    #[test]
+    fn queries_in_reading_order_find_reordered_text() {
+        let shown = visual("שלום עולם");
+        assert_eq!(shown, "םלוע םולש");
+        assert_eq!(find_matches(&shown, "עולם"), vec![0..4]);
+        assert_eq!(find_matches(&shown, "לו"), [1..3, 6..8]);
+
+        // Part of a word still matches, though its letters join differently there
+        let arabic = "\u{0628}\u{0627}\u{0628}\u{0627}";
+        let shown = visual(arabic);
+        assert_eq!(find_matches(&shown, "\u{0628}\u{0627}"), [0..2, 2..4]);
+        assert_eq!(find_matches("Page 2", "page"), vec![0..4]);
+    }
+
+    #[test]
+    fn markdown_markup_stays_in_place() {
+        let markdown = "# שלום\n\n- [עולם](https://example.com)\n\n```\nשלום\n```\n";
+        assert_eq!(
+            visual_markdown(markdown, &metrics(80.0)),
+            "# םולש\n\n- [םלוע](<https://example.com>)\n\n```\nשלום\n```\n"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use ammonia::Url;
+use quick_xml::escape::resolve_xml_entity;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::markdown;
+
+/// A post listed in the blog index
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Post {
+    /// Used in `#/blog/<slug>`; made from the title when the index has none
+    #[serde(default)]
+    pub slug: String,
+    pub title: String,
+    /// ISO 8601 date or timestamp
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Where the post's markdown is fetched from
+    pub url: String,
+}
+
+impl Post {
+    /// Date part of the timestamp, e.g. "2024-05-01"
+    pub fn display_date(&self) -> Option<&str> {
+        let date = self.date.as_deref()?;
+        date.split('T').next().filter(|date| !date.is_empty())
+    }
+}
+
+/// Read a blog index, either a JSON list of posts or an Atom feed.
+/// Posts come back newest first, with absolute URLs and unique slugs.
+pub fn parse_index(body: &str, index_url: &str) -> Result<Vec<Post>, String> {
+    let mut posts = if body.trim_start().starts_with('<') {
+        parse_atom(body)?
+    } else {
+        serde_json::from_str::<Vec<Post>>(body).map_err(|e| format!("Invalid blog index: {}", e))?
+    };
+
+    // ISO 8601 dates sort as text; undated posts go last
+    posts.sort_by(|a, b| b.date.cmp(&a.date));
+
+    let base = Url::parse(index_url).ok();
+    let mut slug_counts: HashMap<String, usize> = HashMap::new();
+    for post in &mut posts {
+        if let Some(url) = base.as_ref().and_then(|base| base.join(&post.url).ok()) {
+            post.url = url.to_string();
+        }
+
+        // Repeated slugs get -2, -3, ... suffixes
+        let mut slug = markdown::slugify(if post.slug.is_empty() { &post.title } else { &post.slug });
+        if slug.is_empty() {
+            slug = "post".to_string();
+        }
+        let count = slug_counts.entry(slug.clone()).or_insert(0);
+        *count += 1;
+        post.slug = if *count == 1 { slug } else { format!("{}-{}", slug, count) };
+    }
+
+    Ok(posts)
+}
+
+/// Entries of an Atom feed. Each entry links to its markdown with
+/// `type="text/markdown"`, or else with its first alternate link.
+fn parse_atom(xml: &str) -> Result<Vec<Post>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut posts = Vec::new();
+    let mut entry: Option<AtomEntry> = None;
+    // Element whose text is being collected, and the text so far
+    let mut field: Option<Vec<u8>> = None;
+    let mut text = String::new();
+
+    loop {
+        let event = reader.read_event().map_err(|e| format!("Invalid Atom feed: {}", e))?;
+        match event {
+            Event::Start(start) => {
+                let name = start.local_name().as_ref().to_vec();
+                match (name.as_slice(), &mut entry) {
+                    (b"entry", _) => entry = Some(AtomEntry::default()),
+                    (b"link", Some(entry)) => entry.add_link(&start),
+                    (b"title" | b"summary" | b"published" | b"updated", Some(_)) if field.is_none() => {
+                        field = Some(name);
+                        text.clear();
+                    }
+                    _ => {}
+                }
+            }
+            Event::Empty(start) => {
+                if let (b"link", Some(entry)) = (start.local_name().as_ref(), &mut entry) {
+                    entry.add_link(&start);
+                }
+            }
+            Event::Text(content) if field.is_some() => {
+                text.push_str(&content.xml10_content().map_err(|e| e.to_string())?);
+            }
+            Event::CData(content) if field.is_some() => {
+                text.push_str(&content.xml10_content().map_err(|e| e.to_string())?);
+            }
+            Event::GeneralRef(reference) if field.is_some() => {
+                let name = reference.decode().map_err(|e| e.to_string())?;
+                match reference.resolve_char_ref().map_err(|e| e.to_string())? {
+                    Some(c) => text.push(c),
+                    None => text.push_str(resolve_xml_entity(&name).unwrap_or_default()),
+                }
+            }
+            Event::End(end) => {
+                let name = end.local_name();
+                if field.as_deref() == Some(name.as_ref()) {
+                    if let Some(entry) = &mut entry {
+                        let value = text.trim().to_string();
+                        match name.as_ref() {
+                            b"title" => entry.post.title = value,
+                            b"summary" => entry.post.summary = Some(value),
+                            b"published" => entry.published = Some(value),
+                            _ => entry.updated = Some(value),
+                        }
+                    }
+                    field = None;
+                } else if name.as_ref() == b"entry" {
+                    posts.extend(entry.take().and_then(AtomEntry::into_post));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(posts)
+}
+
+#[derive(Default)]
+struct AtomEntry {
+    post: Post,
+    published: Option<String>,
+    updated: Option<String>,
+    markdown_link: Option<String>,
+    alternate_link: Option<String>,
+}
+
+impl AtomEntry {
+    fn add_link(&mut self, link: &BytesStart) {
+        let attribute = |name: &str| {
+            link.try_get_attribute(name)
+                .ok()
+                .flatten()
+                .and_then(|attr| attr.normalized_value(XmlVersion::Implicit1_0).ok().map(|value| value.into_owned()))
+        };
+        let Some(href) = attribute("href") else {
+            return;
+        };
+
+        if attribute("type").is_some_and(|t| t == "text/markdown") {
+            self.markdown_link.get_or_insert(href);
+        } else if attribute("rel").map_or(true, |rel| rel == "alternate") {
+            self.alternate_link.get_or_insert(href);
+        }
+    }
+
+    /// Entries without a link have nothing to show
+    fn into_post(self) -> Option<Post> {
+        let url = self.markdown_link.or(self.alternate_link)?;
+        Some(Post {
+            url,
+            date: self.published.or(self.updated),
+            ..self.post
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX_URL: &str = "https://example.com/notes/index.json";
+
+    #[test]
+    fn json_index_is_sorted_and_resolved() {
+        let json = r#"[
+            {"title": "Older", "date": "2023-01-02", "url": "older.md"},
+            {"slug": "newer-post", "title": "Newer", "date": "2024-05-01T10:00:00Z", "summary": "Hi", "url": "/newer.md"},
+            {"title": "Older", "url": "https://cdn.example.com/undated.md"}
+        ]"#;
+        let posts = parse_index(json, INDEX_URL).unwrap();
+
+        let slugs: Vec<&str> = posts.iter().map(|p| p.slug.as_str()).collect();
+        assert_eq!(slugs, ["newer-post", "older", "older-2"]);
+        assert_eq!(posts[0].url, "https://example.com/newer.md");
+        assert_eq!(posts[0].display_date(), Some("2024-05-01"));
+        assert_eq!(posts[1].url, "https://example.com/notes/older.md");
+        assert_eq!(posts[2].url, "https://cdn.example.com/undated.md");
+    }
+
+    #[test]
+    fn atom_entries_prefer_markdown_links() {
+        let atom = r#"<?xml version="1.0" encoding="utf-8"?>
+            <feed xmlns="http://www.w3.org/2005/Atom">
+              <title>Notes</title>
+              <link href="https://example.com/"/>
+              <entry>
+                <title>Tom &amp; Jerry</title>
+                <link rel="alternate" type="text/html" href="https://example.com/tom.html"/>
+                <link rel="alternate" type="text/markdown" href="tom.md"/>
+                <updated>2024-02-01T00:00:00Z</updated>
+                <published>2024-01-01T00:00:00Z</published>
+                <summary><![CDATA[A <b>cat</b> and a mouse]]></summary>
+              </entry>
+              <entry>
+                <title>No link</title>
+              </entry>
+            </feed>"#;
+        let posts = parse_index(atom, INDEX_URL).unwrap();
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].title, "Tom & Jerry");
+        assert_eq!(posts[0].slug, "tom-jerry");
+        assert_eq!(posts[0].url, "https://example.com/notes/tom.md");
+        assert_eq!(posts[0].display_date(), Some("2024-01-01"));
+        assert_eq!(posts[0].summary.as_deref(), Some("A <b>cat</b> and a mouse"));
+    }
+
+    #[test]
+    fn invalid_index_is_an_error() {
+        assert!(parse_index("{\"posts\": 1}", INDEX_URL).is_err());
+        assert!(parse_index("<feed><entry></feed>", INDEX_URL).is_err());
+    }
+}
@@ -0,0 +1,349 @@
+//! `megui check`: the config, artworks index and resume loaded the way the app
+//! loads them, with every problem found reported at once.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::artwork::{self, Artwork, IndexError};
+use crate::components::{MotionMode, ThemeMode};
+use crate::config::Config;
+use crate::fetch::{Fetch, FetchResult};
+use crate::i18n::{self, I18n};
+use crate::links::LinkResolver;
+use crate::resume::{ResumeDocument, ResumeFormat};
+use crate::routes::Route;
+use crate::sanitize::Sanitizer;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Ok,
+    /// Worth a look, but the site works
+    Warning,
+    /// Something the app will fail to show
+    Error,
+}
+
+#[derive(Debug)]
+pub struct Finding {
+    pub level: Level,
+    /// What was checked: "config", "artworks" or "resume"
+    pub area: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|f| f.level == Level::Error)
+    }
+
+    fn count(&self, level: Level) -> usize {
+        self.findings.iter().filter(|f| f.level == level).count()
+    }
+
+    fn push(&mut self, level: Level, area: &'static str, message: impl Into<String>) {
+        self.findings.push(Finding {
+            level,
+            area,
+            message: message.into(),
+        });
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for finding in &self.findings {
+            let level = match finding.level {
+                Level::Ok => "ok",
+                Level::Warning => "warning",
+                Level::Error => "error",
+            };
+            writeln!(f, "{:<8} {:<9} {}", level, finding.area, finding.message)?;
+        }
+        let (errors, warnings) = (self.count(Level::Error), self.count(Level::Warning));
+        writeln!(f, "{} error{}, {} warning{}", errors, plural(errors), warnings, plural(warnings))
+    }
+}
+
+/// Check `config` (or why it couldn't be loaded) and what it points to,
+/// fetching through `fetcher`
+pub fn run(config: Result<Config, String>, fetcher: &dyn Fetch) -> Report {
+    let mut report = Report::default();
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => {
+            report.push(Level::Error, "config", e);
+            return report;
+        }
+    };
+
+    check_config(&config, &mut report);
+    check_artworks(&config, fetcher, &mut report);
+    check_resume(&config, fetcher, &mut report);
+    report
+}
+
+fn check_config(config: &Config, report: &mut Report) {
+    let before = report.findings.len();
+
+    let mut slugs = HashSet::new();
+    for page in &config.pages {
+        if !slugs.insert(page.slug.as_str()) {
+            report.push(Level::Error, "config", format!("Page slug {:?} is used twice", page.slug));
+        } else if !matches!(Route::from_slug(&page.slug, config), Some(Route::Page(_))) {
+            report.push(Level::Error, "config", format!("Page slug {:?} is taken by a built-in page", page.slug));
+        }
+        if page.markdown.is_none() && page.url.is_none() {
+            report.push(Level::Warning, "config", format!("Page {:?} has neither markdown nor url", page.slug));
+        }
+    }
+
+    // Unknown values quietly fall back to the default, so flag them here
+    let app = &config.app;
+    let settings = [
+        ("default_theme", &app.default_theme, "auto", ThemeMode::from_str(&app.default_theme) != ThemeMode::Auto),
+        ("default_motion", &app.default_motion, "auto", MotionMode::from_str(&app.default_motion) != MotionMode::Auto),
+        ("resume_format", &app.resume_format, "html", ResumeFormat::from_str(&app.resume_format) != ResumeFormat::Html),
+    ];
+    for (name, value, default, known) in settings {
+        if !known && !value.eq_ignore_ascii_case(default) {
+            report.push(Level::Warning, "config", format!("Unknown {} {:?}; the default is used", name, value));
+        }
+    }
+
+    for link in &config.links {
+        if link.url.trim().is_empty() {
+            report.push(Level::Error, "config", format!("Link {:?} has no url", link.label));
+        }
+    }
+
+    if report.findings.len() == before {
+        report.push(
+            Level::Ok,
+            "config",
+            format!("{} pages, {} links, {} locales", config.pages.len(), config.links.len(), config.locales.len()),
+        );
+    }
+}
+
+fn check_artworks(config: &Config, fetcher: &dyn Fetch, report: &mut Report) {
+    let url = &config.app.artworks;
+    let Some(text) = fetch_text(fetcher, url, "artworks", report) else {
+        return;
+    };
+
+    let artworks = match artwork::parse_index(&text) {
+        Ok(artworks) => artworks,
+        Err(IndexError::Empty) => {
+            report.push(Level::Error, "artworks", format!("{} lists no artworks folder", url));
+            return;
+        }
+        Err(IndexError::Invalid(e)) => {
+            report.push(Level::Error, "artworks", format!("{} doesn't parse: {}", url, e));
+            report_malformed_entries(&text, report);
+            return;
+        }
+    };
+
+    let mut names = HashSet::new();
+    for artwork in &artworks {
+        if artwork.name.trim().is_empty() {
+            report.push(Level::Error, "artworks", "An artwork has an empty name");
+        } else if !names.insert(artwork.name.as_str()) {
+            report.push(Level::Error, "artworks", format!("{:?} is listed twice", artwork.name));
+        }
+    }
+    check_thumbnails(url, &artworks, fetcher, report);
+
+    report.push(Level::Ok, "artworks", format!("{} artworks in {}", artworks.len(), url));
+}
+
+/// Say which entries keep the index from parsing
+fn report_malformed_entries(text: &str, report: &mut Report) {
+    let Ok(serde_json::Value::Array(folders)) = serde_json::from_str(text) else {
+        return;
+    };
+    let Some(contents) = folders.first().and_then(|folder| folder.get("contents")).and_then(|c| c.as_array()) else {
+        return;
+    };
+    for (idx, entry) in contents.iter().enumerate() {
+        if let Err(e) = serde_json::from_value::<Artwork>(entry.clone()) {
+            report.push(Level::Error, "artworks", format!("Entry {}: {} in {}", idx + 1, e, entry));
+        }
+    }
+}
+
+fn check_thumbnails(index_url: &str, artworks: &[Artwork], fetcher: &dyn Fetch, report: &mut Report) {
+    // Fetch them all at once, then wait for each
+    let pending: Vec<_> = artworks
+        .iter()
+        .map(|artwork| {
+            let url = artwork.thumbnail_url(index_url);
+            let receiver = url.as_ref().map(|url| fetcher.receive(url, None));
+            (artwork, url, receiver)
+        })
+        .collect();
+
+    for (artwork, url, receiver) in pending {
+        let (Some(url), Some(receiver)) = (url, receiver) else {
+            report.push(Level::Warning, "artworks", format!("{:?} has no thumbnail", artwork.name));
+            continue;
+        };
+        let problem = match receiver.recv() {
+            Ok(Ok(response)) if response.ok => match image::load_from_memory(&response.bytes) {
+                Ok(_) => continue,
+                Err(e) => format!("isn't an image: {}", e),
+            },
+            Ok(Ok(response)) => format!("{} {}", response.status, response.status_text),
+            Ok(Err(e)) => e,
+            Err(_) => "no response".to_string(),
+        };
+        report.push(
+            Level::Error,
+            "artworks",
+            format!("Thumbnail of {:?} at {}: {}", artwork.name, url, problem),
+        );
+    }
+}
+
+fn check_resume(config: &Config, fetcher: &dyn Fetch, report: &mut Report) {
+    let url = &config.app.resume;
+    let Some(text) = fetch_text(fetcher, url, "resume", report) else {
+        return;
+    };
+
+    let format = ResumeFormat::from_str(&config.app.resume_format);
+    let sanitizer = Sanitizer::new(&config.sanitize, url);
+    let links = LinkResolver::new(url, &config.app.website);
+    match ResumeDocument::parse(format, &text, &sanitizer, &links) {
+        Ok(document) => {
+            // Only counted, so the locale doesn't matter
+            let sections = document.outline(&I18n::new(i18n::AUTO, config)).len();
+            if sections == 0 {
+                report.push(Level::Warning, "resume", format!("{} has no headings for the outline", url));
+            }
+            report.push(Level::Ok, "resume", format!("{} sections in {}", sections, url));
+        }
+        Err(e) => report.push(Level::Error, "resume", format!("{}: {}", url, e)),
+    }
+}
+
+/// Body of `url` as text, or `None` with the reason reported
+fn fetch_text(fetcher: &dyn Fetch, url: &str, area: &'static str, report: &mut Report) -> Option<String> {
+    let result: FetchResult = fetcher
+        .receive(url, None)
+        .recv()
+        .unwrap_or_else(|_| Err("no response".to_string()));
+    let problem = match result {
+        Ok(response) if response.ok => match response.text() {
+            Some(text) => return Some(text.to_string()),
+            None => "not text".to_string(),
+        },
+        Ok(response) => format!("{} {}", response.status, response.status_text),
+        Err(e) => e,
+    };
+    report.push(Level::Error, area, format!("{}: {}", url, problem));
+    None
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::FixtureFetch;
+
+    /// A 1×1 PNG
+    fn png() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image::RgbaImage::new(1, 1)
+            .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    fn errors(report: &Report) -> Vec<&str> {
+        report
+            .findings
+            .iter()
+            .filter(|f| f.level == Level::Error)
+            .map(|f| f.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn content_problems_are_reported() {
+        let config = Config::default();
+        let base = config.app.artworks.trim_end_matches("/index.json").to_string();
+        let index = r#"[{"type": "directory", "name": ".", "contents": [
+            {"name": "alpha", "thumbnail": "thumb.png"},
+            {"name": "beta", "thumbnail": "thumb.png"},
+            {"name": "gamma"}
+        ]}]"#;
+        let fixtures = FixtureFetch::new()
+            .with(&config.app.artworks, index)
+            .with(&format!("{}/alpha/thumb.png", base), png())
+            .with(&config.app.resume, "<h1>Ada</h1><h2>Work</h2>");
+
+        let report = run(Ok(config.clone()), &fixtures);
+        let errors = errors(&report);
+        assert_eq!(errors.len(), 1, "{}", report);
+        assert!(errors[0].contains("\"beta\"") && errors[0].contains("404"));
+        assert!(report.findings.iter().any(|f| f.level == Level::Warning && f.message.contains("gamma")));
+        assert!(report.has_errors());
+
+        let malformed = r#"[{"type": "directory", "name": ".", "contents": [{"name": "alpha"}, {"info": "no name"}]}]"#;
+        let fixtures = FixtureFetch::new().with(&config.app.artworks, malformed);
+        let report = run(Ok(config), &fixtures);
+        let errors = self::errors(&report);
+        assert!(errors.iter().any(|e| e.starts_with("Entry 2: missing field `name`")), "{}", report);
+        assert!(errors.iter().any(|e| e.contains(&format!("{}: 404", Config::default().app.resume))));
+    }
+
+    #[test]
+    fn clean_content_passes() {
+        let config = Config::default();
+        let fixtures = FixtureFetch::new()
+            .with(&config.app.artworks, r#"[{"type": "directory", "name": ".", "contents": []}]"#)
+            .with(&config.app.resume, "<h1>Ada</h1>");
+
+        let report = run(Ok(config), &fixtures);
+        assert!(!report.has_errors(), "{}", report);
+        assert!(report.to_string().ends_with("0 errors, 0 warnings\n"));
+        assert!(run(Err("config.toml: bad".to_string()), &fixtures).has_errors());
+    }
+
+    #[test]
+    fn settings_are_checked_against_their_own_default() {
+        let mut config = Config::default();
+        config.app.default_theme = "html".to_string();
+        config.app.default_motion = "Auto".to_string();
+        config.app.resume_format = "auto".to_string();
+
+        let mut report = Report::default();
+        check_config(&config, &mut report);
+        let warnings: Vec<&str> = report
+            .findings
+            .iter()
+            .filter(|f| f.level == Level::Warning)
+            .map(|f| f.message.as_str())
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                "Unknown default_theme \"html\"; the default is used",
+                "Unknown resume_format \"auto\"; the default is used"
+            ]
+        );
+    }
+}
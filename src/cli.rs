@@ -0,0 +1,244 @@
+//! Command-line options of the native app
+
+use clap::{Parser, Subcommand};
+use eframe::egui;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::app::StartOptions;
+use crate::check;
+use crate::index;
+use crate::config::Config;
+use crate::fetch::{self, Fetch};
+use crate::routes::Route;
+use crate::window::{self, Monitors};
+
+/// Identifies the app to the OS and names its settings folder, whatever the
+/// window title
+pub const APP_ID: &str = "megui";
+
+/// Window size when `--window-size` isn't given
+const DEFAULT_WINDOW_SIZE: egui::Vec2 = egui::vec2(800.0, 600.0);
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Portfolio of artworks, resume and pages")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// config.toml to use instead of the built-in one
+    #[arg(long, value_name = "PATH", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Page to open, as in the web app's URL, e.g. "#/resume/experience"
+    #[arg(long, value_name = "HASH")]
+    pub route: Option<String>,
+
+    /// Color theme, instead of the config's default_theme
+    #[arg(long, value_parser = ["auto", "light", "dark", "high_contrast"])]
+    pub theme: Option<String>,
+
+    /// Start fullscreen
+    #[arg(long)]
+    pub fullscreen: bool,
+
+    /// Fullscreen without window decorations, with the menu and settings hidden
+    #[arg(long)]
+    pub kiosk: bool,
+
+    /// Window size in points, e.g. 1280x720
+    #[arg(long, value_name = "WIDTHxHEIGHT", value_parser = parse_size)]
+    pub window_size: Option<egui::Vec2>,
+
+    /// Artworks index to load instead of the config's
+    #[arg(long, value_name = "URL", conflicts_with = "dir", global = true)]
+    pub artworks_url: Option<String>,
+
+    /// Show the artworks in a local folder: its index.json, or else one
+    /// artwork per subfolder
+    #[arg(long, value_name = "PATH", global = true)]
+    pub dir: Option<PathBuf>,
+
+    /// Save every response to this folder as it is fetched
+    #[arg(long, value_name = "DIR", env = "MEGUI_RECORD", conflicts_with = "replay", global = true)]
+    pub record: Option<PathBuf>,
+
+    /// Answer requests from a folder saved with --record, without the network
+    #[arg(long, value_name = "DIR", env = "MEGUI_REPLAY", global = true)]
+    pub replay: Option<PathBuf>,
+}
+
+/// Things to do instead of opening the window
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Check the config, artworks index and resume, and exit with an error
+    /// if the app would fail to show any of them
+    Check,
+
+    /// Write DIR/index.json listing its subfolders as artworks, with the
+    /// metadata in each one's artwork.toml and a generated thumbnail
+    Index {
+        /// Folder with one subfolder per artwork
+        dir: PathBuf,
+
+        /// Largest width and height of thumbnails, in pixels
+        #[arg(long, value_name = "PX", default_value_t = 400)]
+        thumbnail_size: u32,
+    },
+}
+
+impl Cli {
+    /// Run `command`, returning the process exit code
+    pub fn run(&self, command: &Command) -> i32 {
+        match command {
+            Command::Check => {
+                let config = self.start_options().map(|options| options.config);
+                let report = check::run(config, self.fetcher().as_ref());
+                print!("{}", report);
+                i32::from(report.has_errors())
+            }
+            Command::Index { dir, thumbnail_size } => match index::write(dir, *thumbnail_size) {
+                Ok(index) => {
+                    for warning in &index.warnings {
+                        eprintln!("warning: {}", warning);
+                    }
+                    println!("{} artworks in {}", index.entries.len(), dir.join(index::INDEX_FILE).display());
+                    0
+                }
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    1
+                }
+            },
+        }
+    }
+
+    /// The config with the overrides applied, and where the app starts
+    pub fn start_options(&self) -> Result<StartOptions, String> {
+        let mut config = match &self.config {
+            Some(path) => Config::from_file(path)?,
+            None => Config::load(),
+        };
+        if let Some(theme) = &self.theme {
+            config.app.default_theme = theme.clone();
+        }
+        if let Some(url) = &self.artworks_url {
+            config.app.artworks = url.clone();
+        }
+        if let Some(dir) = &self.dir {
+            let dir = dir.canonicalize().map_err(|e| format!("{}: {}", dir.display(), e))?;
+            config.app.artworks = fetch::file_url(&dir.join("index.json"));
+        }
+        if let Some(hash) = &self.route {
+            if Route::from_hash(hash, &config).is_none() {
+                return Err(format!("No page at route {:?}", hash));
+            }
+        }
+
+        Ok(StartOptions {
+            config,
+            route: self.route.clone(),
+            kiosk: self.kiosk,
+        })
+    }
+
+    /// Window titled after the site, where it was last time unless options
+    /// say otherwise, and on one of `monitors`
+    pub fn native_options(&self, config: &Config, monitors: Monitors) -> eframe::NativeOptions {
+        let (window_size, fullscreen) = (self.window_size, self.fullscreen || self.kiosk);
+        eframe::NativeOptions {
+            viewport: self
+                .viewport()
+                .with_title(&config.app.name)
+                .with_app_id(APP_ID)
+                .with_icon(window::icon(&config.app.name)),
+            // Runs after the remembered window state is applied
+            window_builder: Some(Box::new(move |mut viewport| {
+                if let Some(size) = window_size {
+                    viewport = viewport.with_inner_size(size).with_maximized(false);
+                }
+                if fullscreen {
+                    viewport = viewport.with_fullscreen(true);
+                }
+                let monitors = monitors.lock().map(|m| m.clone()).unwrap_or_default();
+                window::keep_on_screen(viewport, &monitors)
+            })),
+            // A kiosk's fullscreen shouldn't carry over to the next launch
+            persist_window: !self.kiosk,
+            ..Default::default()
+        }
+    }
+
+    /// The window on a first launch
+    fn viewport(&self) -> egui::ViewportBuilder {
+        let viewport = egui::ViewportBuilder::default()
+            .with_inner_size(self.window_size.unwrap_or(DEFAULT_WINDOW_SIZE))
+            .with_fullscreen(self.fullscreen || self.kiosk);
+        match self.kiosk {
+            true => viewport.with_decorations(false),
+            false => viewport,
+        }
+    }
+
+    /// The network, recorded or replayed as asked, with `file://` URLs read from disk
+    pub fn fetcher(&self) -> Arc<dyn Fetch> {
+        let upstream: Arc<dyn Fetch> = match (&self.replay, &self.record) {
+            (Some(dir), _) => Arc::new(fetch::RecordedFetch::replay(dir)),
+            (None, Some(dir)) => Arc::new(fetch::RecordedFetch::record(dir, Arc::new(fetch::HttpFetch))),
+            (None, None) => Arc::new(fetch::HttpFetch),
+        };
+        Arc::new(fetch::LocalFetch::new(upstream))
+    }
+}
+
+/// `1280x720` as a size
+fn parse_size(text: &str) -> Result<egui::Vec2, String> {
+    let invalid = || format!("expected WIDTHxHEIGHT, like 1280x720, not {:?}", text);
+    let (width, height) = text.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let width: f32 = width.trim().parse().map_err(|_| invalid())?;
+    let height: f32 = height.trim().parse().map_err(|_| invalid())?;
+    if width < 1.0 || height < 1.0 {
+        return Err(invalid());
+    }
+    Ok(egui::vec2(width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("megui").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn options_override_the_config() {
+        let cli = parse(&[
+            "--route",
+            "#/resume/experience",
+            "--theme",
+            "dark",
+            "--artworks-url",
+            "https://a.test/index.json",
+            "--window-size",
+            "1280x720",
+            "--kiosk",
+        ])
+        .unwrap();
+        assert_eq!(cli.window_size, Some(egui::vec2(1280.0, 720.0)));
+
+        let options = cli.start_options().unwrap();
+        assert_eq!(options.config.app.default_theme, "dark");
+        assert_eq!(options.config.app.artworks, "https://a.test/index.json");
+        assert_eq!(options.route.as_deref(), Some("#/resume/experience"));
+        assert!(options.kiosk);
+    }
+
+    #[test]
+    fn bad_options_are_refused() {
+        assert!(parse(&["--window-size", "wide"]).is_err());
+        assert!(parse(&["--theme", "sepia"]).is_err());
+        assert!(parse(&["--dir", ".", "--artworks-url", "https://a.test/"]).is_err());
+        assert!(parse(&["--route", "#/nowhere"]).unwrap().start_options().is_err());
+    }
+}
@@ -0,0 +1,25 @@
+use eframe::egui::{self, accesskit};
+
+/// Expose a `Ui` to assistive tech as a landmark, list, etc.
+pub fn set_role(ui: &egui::Ui, role: accesskit::Role, label: Option<&str>) {
+    ui.ctx().accesskit_node_builder(ui.unique_id(), |node| {
+        node.set_role(role);
+        if let Some(label) = label {
+            node.set_label(label);
+        }
+    });
+}
+
+/// Give an icon-only (or emoji-prefixed) widget a spoken label
+pub fn set_label(response: &egui::Response, label: &str) {
+    response.ctx.accesskit_node_builder(response.id, |node| {
+        node.set_label(label);
+    });
+}
+
+/// Keep a `Ui` and everything in it out of the accessibility tree
+pub fn set_hidden(ui: &egui::Ui) {
+    ui.ctx().accesskit_node_builder(ui.unique_id(), |node| {
+        node.set_hidden();
+    });
+}
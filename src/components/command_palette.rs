@@ -0,0 +1,217 @@
+use eframe::egui;
+
+use crate::components::ThemeMode;
+use crate::i18n::I18n;
+use crate::routes::Route;
+
+const MAX_RESULTS: usize = 12;
+
+/// What running a palette entry does
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Navigate(Route),
+    OpenArtwork(String),
+    ResumeSection(String),
+    BlogPost(String),
+    SetTheme(ThemeMode),
+    OpenArtworksWebsite,
+    ReloadData,
+}
+
+pub struct PaletteEntry {
+    pub label: String,
+    pub detail: Option<String>,
+    pub command: Command,
+}
+
+impl PaletteEntry {
+    pub fn new(label: impl Into<String>, command: Command) -> Self {
+        Self {
+            label: label.into(),
+            detail: None,
+            command,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Matches on the label rank above matches on the detail text
+    fn score(&self, query: &str) -> Option<i32> {
+        let label_score = fuzzy_score(query, &self.label).map(|s| s + 1000);
+        let detail_score = self.detail.as_deref().and_then(|d| fuzzy_score(query, d));
+        label_score.max(detail_score)
+    }
+}
+
+/// Subsequence match score: every query character must appear in order.
+/// Consecutive matches and matches at word starts score higher.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut query_idx = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for (idx, c) in text.iter().enumerate() {
+        if query_idx < query.len() && *c == query[query_idx] {
+            score += 1;
+            if previous_match == Some(idx.wrapping_sub(1)) {
+                score += 5;
+            }
+            if idx == 0 || !text[idx - 1].is_alphanumeric() {
+                score += 10;
+            }
+            previous_match = Some(idx);
+            query_idx += 1;
+        }
+    }
+
+    if query_idx < query.len() {
+        return None;
+    }
+
+    // Prefer shorter texts for equally good matches
+    Some(score * 10 - text.len() as i32)
+}
+
+/// Ctrl+K overlay that fuzzy-searches routes, content and actions
+#[derive(Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    query: String,
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Returns the command to run if an entry was chosen
+    pub fn render(&mut self, ctx: &egui::Context, i18n: &I18n, entries: &[PaletteEntry]) -> Option<Command> {
+        if !self.open {
+            return None;
+        }
+
+        let mut results: Vec<(i32, &PaletteEntry)> = entries
+            .iter()
+            .filter_map(|entry| entry.score(&self.query).map(|score| (score, entry)))
+            .collect();
+        if !self.query.trim().is_empty() {
+            results.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        }
+        results.truncate(MAX_RESULTS);
+
+        // List navigation, consumed before the text field sees the keys
+        let (down, up, enter) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            )
+        });
+        if down && self.selected + 1 < results.len() {
+            self.selected += 1;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        self.selected = self.selected.min(results.len().saturating_sub(1));
+
+        let mut chosen = None;
+
+        let modal = egui::Modal::new(egui::Id::new("command_palette")).show(ctx, |ui| {
+            ui.set_width(500.0);
+
+            let search = ui.add(
+                egui::TextEdit::singleline(&mut self.query)
+                    .hint_text(i18n.tr("palette-hint"))
+                    .desired_width(f32::INFINITY),
+            );
+            search.request_focus();
+            if search.changed() {
+                self.selected = 0;
+            }
+
+            ui.add_space(5.0);
+            ui.separator();
+
+            if results.is_empty() {
+                ui.label(egui::RichText::new(i18n.tr("no-matches")).weak());
+            }
+
+            for (idx, (_, entry)) in results.iter().enumerate() {
+                let selected = idx == self.selected;
+                let response = ui.selectable_label(selected, &entry.label);
+                if let Some(detail) = &entry.detail {
+                    ui.label(egui::RichText::new(detail).small().weak());
+                }
+                if selected {
+                    response.scroll_to_me(None);
+                }
+                if response.clicked() {
+                    chosen = Some(entry.command.clone());
+                }
+            }
+
+            if enter {
+                if let Some((_, entry)) = results.get(self.selected) {
+                    chosen = Some(entry.command.clone());
+                }
+            }
+        });
+
+        if chosen.is_some() || modal.should_close() {
+            self.toggle();
+        }
+
+        chosen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_scores() {
+        let cases: [(&str, &str, Option<i32>); 8] = [
+            // Empty queries match everything equally
+            ("", "Resume", Some(0)),
+            ("  ", "Resume", Some(0)),
+            // Consecutive letters at a word start: 1 + 10, then 1 + 5 each
+            ("res", "Resume", Some((11 + 6 + 6) * 10 - 6)),
+            // Case is ignored
+            ("RES", "resume", Some((11 + 6 + 6) * 10 - 6)),
+            // Letters in order but apart, the second at a word start
+            ("ga", "Go to Artworks", Some((11 + 11) * 10 - 14)),
+            // Spaces in the query are skipped
+            ("g a", "Go to Artworks", Some((11 + 11) * 10 - 14)),
+            ("sr", "Resume", None),
+            ("résumé", "Resume", None),
+        ];
+        for (query, text, expected) in cases {
+            assert_eq!(fuzzy_score(query, text), expected, "{query:?} in {text:?}");
+        }
+    }
+
+    #[test]
+    fn better_matches_rank_higher() {
+        let score = |query, text| fuzzy_score(query, text).unwrap();
+        // Word starts beat letters inside words
+        assert!(score("ar", "Artworks: rain") > score("ar", "Dark theme"));
+        // Runs beat scattered letters
+        assert!(score("them", "Theme: dark") > score("them", "The Hermit"));
+        // Shorter texts win ties
+        assert!(score("blog", "Blog") > score("blog", "Blog: first post"));
+    }
+}
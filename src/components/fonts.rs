@@ -0,0 +1,121 @@
+use eframe::egui;
+use std::collections::BTreeMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+use crate::config::{FontFile, FontsConfig};
+use crate::fetch::Fetch;
+
+type FontResult = (String, Result<Vec<u8>, String>);
+
+/// Loads the font files listed in config and installs them once they are all available
+pub struct FontLoader {
+    config: FontsConfig,
+    receiver: Option<Receiver<FontResult>>,
+    pending: usize,
+    loaded: BTreeMap<String, Vec<u8>>,
+}
+
+impl FontLoader {
+    pub fn new(config: &FontsConfig) -> Self {
+        Self {
+            config: config.clone(),
+            receiver: None,
+            pending: 0,
+            loaded: BTreeMap::new(),
+        }
+    }
+
+    pub fn start(&mut self, ctx: &egui::Context, fetcher: &dyn Fetch) {
+        if self.config.files.is_empty() {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        self.receiver = Some(receiver);
+        self.pending = self.config.files.len();
+
+        for file in &self.config.files {
+            Self::load_file(ctx, fetcher, file, sender.clone());
+        }
+    }
+
+    fn load_file(ctx: &egui::Context, fetcher: &dyn Fetch, file: &FontFile, sender: Sender<FontResult>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let is_url = file.source.starts_with("http://") || file.source.starts_with("https://");
+            if !is_url {
+                let result = std::fs::read(&file.source).map_err(|e| e.to_string());
+                let _ = sender.send((file.name.clone(), result));
+                return;
+            }
+        }
+
+        let ctx = ctx.clone();
+        let name = file.name.clone();
+        fetcher.fetch(
+            &file.source,
+            Box::new(move |result| {
+                let result = match result {
+                    Ok(response) if response.ok => Ok(response.bytes),
+                    Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+                    Err(e) => Err(e),
+                };
+                let _ = sender.send((name, result));
+                ctx.request_repaint();
+            }),
+        );
+    }
+
+    /// Collect finished loads and install the fonts once nothing is pending
+    pub fn poll(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+
+        while let Ok((name, result)) = receiver.try_recv() {
+            self.pending -= 1;
+            match result {
+                Ok(bytes) => {
+                    self.loaded.insert(name, bytes);
+                }
+                Err(e) => log::warn!("Failed to load font {}: {}", name, e),
+            }
+        }
+
+        if self.pending == 0 {
+            self.receiver = None;
+            ctx.set_fonts(self.font_definitions());
+        }
+    }
+
+    /// egui's default fonts with the configured fonts prepended to each family,
+    /// so anything missing from a custom font falls back down the chain
+    fn font_definitions(&self) -> egui::FontDefinitions {
+        let mut fonts = egui::FontDefinitions::default();
+
+        for (name, bytes) in &self.loaded {
+            fonts.font_data.insert(
+                name.clone(),
+                Arc::new(egui::FontData::from_owned(bytes.clone())),
+            );
+        }
+
+        let chains = [
+            (egui::FontFamily::Proportional, &self.config.proportional),
+            (egui::FontFamily::Monospace, &self.config.monospace),
+        ];
+        for (family, chain) in chains {
+            let names = chain
+                .iter()
+                .filter(|name| self.loaded.contains_key(*name))
+                .cloned();
+            let entry = fonts.families.entry(family).or_default();
+            let defaults = std::mem::take(entry);
+            entry.extend(names);
+            entry.extend(defaults);
+        }
+
+        fonts
+    }
+}
@@ -1,5 +1,16 @@
+pub mod a11y;
 pub mod theme;
+pub mod command_palette;
 pub mod sidebar;
 pub mod settings;
+pub mod typography;
+pub mod fonts;
+pub mod motion;
+pub mod shortcuts;
 
 pub use theme::ThemeMode;
+pub use command_palette::{Command, CommandPalette, PaletteEntry};
+pub use typography::Typography;
+pub use fonts::FontLoader;
+pub use motion::MotionMode;
+pub use shortcuts::{Action, Keymap, ShortcutsOverlay};
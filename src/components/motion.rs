@@ -0,0 +1,64 @@
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::I18n;
+
+/// egui's default `Style::animation_time`
+const DEFAULT_ANIMATION_TIME: f32 = 1.0 / 12.0;
+
+/// Whether UI transitions are animated
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MotionMode {
+    Auto,
+    Full,
+    Reduced,
+}
+
+impl MotionMode {
+    pub const STORAGE_KEY: &'static str = "motion_mode";
+
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "full" => MotionMode::Full,
+            "reduced" => MotionMode::Reduced,
+            _ => MotionMode::Auto,
+        }
+    }
+
+    pub fn is_reduced(&self) -> bool {
+        match self {
+            MotionMode::Full => false,
+            MotionMode::Reduced => true,
+            MotionMode::Auto => {
+                // Follow `prefers-reduced-motion` on web, animate on native
+                #[cfg(target_arch = "wasm32")]
+                {
+                    crate::components::theme::media_query_matches("(prefers-reduced-motion: reduce)")
+                        .unwrap_or(false)
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Disable egui's built-in animations (collapsing headers, windows, scrolling)
+    pub fn apply(&self, ctx: &egui::Context) {
+        let animation_time = if self.is_reduced() {
+            0.0
+        } else {
+            DEFAULT_ANIMATION_TIME
+        };
+        ctx.style_mut(|style| style.animation_time = animation_time);
+    }
+
+    pub fn label<'a>(&self, i18n: &'a I18n) -> &'a str {
+        i18n.tr(match self {
+            MotionMode::Auto => "motion-auto",
+            MotionMode::Full => "motion-full",
+            MotionMode::Reduced => "motion-reduced",
+        })
+    }
+}
@@ -1,42 +1,143 @@
 use eframe::egui;
 
-use crate::components::ThemeMode;
+use crate::components::{MotionMode, ThemeMode, Typography};
 use crate::config::Config;
+use crate::i18n::{self, I18n};
 
 pub struct SettingsModal;
 
 impl SettingsModal {
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         ctx: &egui::Context,
         config: &Config,
+        i18n: &I18n,
+        locale: &mut String,
         theme_mode: &mut ThemeMode,
+        motion_mode: &mut MotionMode,
+        typography: &mut Typography,
         open: &mut bool,
     ) {
-        egui::Window::new("⚙ Settings")
+        egui::Window::new(i18n.tr("settings-title"))
+            .id(egui::Id::new("settings_window"))
             .open(open)
             .resizable(false)
             .default_width(400.0)
             .show(ctx, |ui| {
-                ui.heading("Theme");
+                ui.heading(i18n.tr("settings-language"));
+                ui.add_space(5.0);
+
+                let locales = i18n.available();
+                let current = locales
+                    .iter()
+                    .find(|(code, _)| code == i18n.locale())
+                    .map_or(i18n.locale(), |(_, name)| name.as_str());
+                let selected_text = if *locale == i18n::AUTO {
+                    format!("{} ({})", i18n.tr("settings-language-auto"), current)
+                } else {
+                    current.to_string()
+                };
+                egui::ComboBox::from_id_salt("locale_select")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(locale, i18n::AUTO.to_string(), i18n.tr("settings-language-auto"));
+                        for (code, name) in locales {
+                            ui.selectable_value(locale, code.clone(), name);
+                        }
+                    });
+
+                ui.add_space(15.0);
+                ui.separator();
+                ui.add_space(15.0);
+
+                ui.heading(i18n.tr("settings-theme"));
                 ui.add_space(5.0);
 
                 ui.horizontal(|ui| {
-                    if ui.selectable_label(*theme_mode == ThemeMode::Auto, "Auto").clicked() {
-                        *theme_mode = ThemeMode::Auto;
+                    for mode in [ThemeMode::Auto, ThemeMode::Light, ThemeMode::Dark, ThemeMode::HighContrast] {
+                        if ui.selectable_label(*theme_mode == mode, mode.label(i18n)).clicked() {
+                            *theme_mode = mode;
+                        }
                     }
-                    if ui.selectable_label(*theme_mode == ThemeMode::Light, "Light").clicked() {
-                        *theme_mode = ThemeMode::Light;
+                });
+
+                ui.add_space(15.0);
+                ui.separator();
+                ui.add_space(15.0);
+
+                ui.heading(i18n.tr("settings-motion"));
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    for mode in [MotionMode::Auto, MotionMode::Full, MotionMode::Reduced] {
+                        if ui.selectable_label(*motion_mode == mode, mode.label(i18n)).clicked() {
+                            *motion_mode = mode;
+                        }
                     }
-                    if ui.selectable_label(*theme_mode == ThemeMode::Dark, "Dark").clicked() {
-                        *theme_mode = ThemeMode::Dark;
+                });
+
+                // The canvas is invisible to browser screen readers, so egui can speak instead
+                #[cfg(target_arch = "wasm32")]
+                {
+                    ui.add_space(5.0);
+                    let mut screen_reader = ctx.options(|o| o.screen_reader);
+                    if ui.checkbox(&mut screen_reader, i18n.tr("settings-read-aloud")).changed() {
+                        ctx.options_mut(|o| o.screen_reader = screen_reader);
                     }
+                }
+
+                ui.add_space(15.0);
+                ui.separator();
+                ui.add_space(15.0);
+
+                ui.heading(i18n.tr("settings-text"));
+                ui.add_space(5.0);
+
+                egui::Grid::new("typography_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label(i18n.tr("settings-font-size"));
+                        ui.add(
+                            egui::Slider::new(&mut typography.font_size, Typography::font_size_range())
+                                .step_by(1.0)
+                                .suffix(" pt"),
+                        );
+                        ui.end_row();
+
+                        ui.label(i18n.tr("settings-ui-zoom"));
+                        ui.add(
+                            egui::Slider::new(&mut typography.ui_zoom, Typography::ui_zoom_range())
+                                .step_by(0.1)
+                                .custom_formatter(|value, _| format!("{:.0}%", value * 100.0)),
+                        );
+                        ui.end_row();
+
+                        ui.label(i18n.tr("settings-line-spacing"));
+                        ui.add(
+                            egui::Slider::new(&mut typography.line_spacing, Typography::line_spacing_range())
+                                .step_by(1.0),
+                        );
+                        ui.end_row();
+                    });
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.button(i18n.tr("settings-reset")).clicked() {
+                        *typography = Typography::from_config(&config.typography);
+                    }
+                    ui.label(
+                        egui::RichText::new(i18n.tr("settings-zoom-hint"))
+                            .small()
+                            .weak(),
+                    );
                 });
 
                 ui.add_space(15.0);
                 ui.separator();
                 ui.add_space(15.0);
 
-                ui.heading("Configuration");
+                ui.heading(i18n.tr("settings-configuration"));
                 ui.add_space(5.0);
 
                 egui::Grid::new("config_grid")
@@ -44,29 +145,33 @@ impl SettingsModal {
                     .spacing([10.0, 8.0])
                     .striped(true)
                     .show(ui, |ui| {
-                        ui.label("Name:");
+                        ui.label(i18n.tr("settings-name"));
                         ui.label(&config.app.name);
                         ui.end_row();
 
-                        ui.label("Website:");
+                        ui.label(i18n.tr("settings-website"));
                         ui.hyperlink_to(&config.app.website, &config.app.website);
                         ui.end_row();
 
-                        ui.label("Resume:");
+                        ui.label(i18n.tr("settings-resume"));
                         ui.hyperlink_to(&config.app.resume, &config.app.resume);
                         ui.end_row();
 
-                        ui.label("Artworks:");
+                        ui.label(i18n.tr("settings-artworks"));
                         let artworks_base = config.app.artworks.trim_end_matches("/index.json");
                         ui.hyperlink_to(artworks_base, artworks_base);
                         ui.end_row();
 
-                        ui.label("Repository:");
+                        ui.label(i18n.tr("settings-repository"));
                         ui.hyperlink_to(&config.app.repository, &config.app.repository);
                         ui.end_row();
 
-                        ui.label("Current Theme:");
-                        ui.label(theme_mode.as_str());
+                        ui.label(i18n.tr("settings-current-theme"));
+                        ui.label(theme_mode.label(i18n));
+                        ui.end_row();
+
+                        ui.label(i18n.tr("settings-current-motion"));
+                        ui.label(motion_mode.label(i18n));
                         ui.end_row();
                     });
 
@@ -0,0 +1,445 @@
+use eframe::egui;
+use std::collections::BTreeMap;
+
+use crate::i18n::I18n;
+
+/// How long the first key of a sequence like "g h" waits for the second
+const SEQUENCE_TIMEOUT: f64 = 1.5;
+
+/// Something a keyboard shortcut can trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    GoHome,
+    GoArtworks,
+    GoResume,
+    GoAbout,
+    FocusSearch,
+    ToggleSidebar,
+    OpenSettings,
+    CloseWindow,
+    ShowShortcuts,
+    CommandPalette,
+}
+
+impl Action {
+    pub const ALL: [Action; 10] = [
+        Action::GoHome,
+        Action::GoArtworks,
+        Action::GoResume,
+        Action::GoAbout,
+        Action::FocusSearch,
+        Action::ToggleSidebar,
+        Action::OpenSettings,
+        Action::CloseWindow,
+        Action::ShowShortcuts,
+        Action::CommandPalette,
+    ];
+
+    /// Name used in the `[shortcuts]` config table
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            Action::GoHome => "go_home",
+            Action::GoArtworks => "go_artworks",
+            Action::GoResume => "go_resume",
+            Action::GoAbout => "go_about",
+            Action::FocusSearch => "focus_search",
+            Action::ToggleSidebar => "toggle_sidebar",
+            Action::OpenSettings => "open_settings",
+            Action::CloseWindow => "close_window",
+            Action::ShowShortcuts => "show_shortcuts",
+            Action::CommandPalette => "command_palette",
+        }
+    }
+
+    pub fn description<'a>(&self, i18n: &'a I18n) -> &'a str {
+        i18n.tr(match self {
+            Action::GoHome => "action-go-home",
+            Action::GoArtworks => "action-go-artworks",
+            Action::GoResume => "action-go-resume",
+            Action::GoAbout => "action-go-about",
+            Action::FocusSearch => "action-focus-search",
+            Action::ToggleSidebar => "action-toggle-sidebar",
+            Action::OpenSettings => "action-open-settings",
+            Action::CloseWindow => "action-close-window",
+            Action::ShowShortcuts => "action-show-shortcuts",
+            Action::CommandPalette => "action-command-palette",
+        })
+    }
+
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::GoHome => &["1", "g h"],
+            Action::GoArtworks => &["2", "g a"],
+            Action::GoResume => &["3", "g r"],
+            Action::GoAbout => &["4", "g b"],
+            Action::FocusSearch => &["/", "ctrl+f"],
+            Action::ToggleSidebar => &["m"],
+            Action::OpenSettings => &[","],
+            Action::CloseWindow => &["escape"],
+            Action::ShowShortcuts => &["?"],
+            Action::CommandPalette => &["ctrl+k"],
+        }
+    }
+}
+
+/// A single key press with modifiers, e.g. "ctrl+k"
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct KeyChord {
+    key: egui::Key,
+    modifiers: egui::Modifiers,
+}
+
+impl KeyChord {
+    fn parse(s: &str) -> Option<Self> {
+        let mut modifiers = egui::Modifiers::NONE;
+        let mut parts: Vec<&str> = s.split('+').collect();
+        let key_name = parts.pop()?;
+
+        for part in parts {
+            match part.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "cmd" | "command" => modifiers.command = true,
+                "alt" | "option" => modifiers.alt = true,
+                "shift" => modifiers.shift = true,
+                _ => return None,
+            }
+        }
+
+        // Accept lowercase names like "escape" as well as egui's "Escape"
+        let key = egui::Key::from_name(key_name)
+            .or_else(|| egui::Key::from_name(&capitalize(key_name)))?;
+        Some(Self { key, modifiers })
+    }
+
+    /// Shift is ignored unless the binding asks for it, since symbols
+    /// like "?" already need shift on most layouts. "ctrl" is the Ctrl key
+    /// everywhere, and "cmd" is Cmd on macOS and Ctrl elsewhere.
+    fn matches(&self, key: egui::Key, modifiers: egui::Modifiers) -> bool {
+        self.key == key && self.modifiers.alt == modifiers.alt && modifiers.matches_logically(self.modifiers)
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+struct Binding {
+    sequence: Vec<KeyChord>,
+    label: String,
+    action: Action,
+}
+
+/// Global keyboard shortcuts, remappable through `[shortcuts]` in config.toml
+pub struct Keymap {
+    bindings: Vec<Binding>,
+    pending: Option<(KeyChord, f64)>,
+}
+
+impl Keymap {
+    pub fn from_config(overrides: &BTreeMap<String, Vec<String>>) -> Self {
+        for name in overrides.keys() {
+            if !Action::ALL.iter().any(|a| a.config_name() == name) {
+                log::warn!("Unknown shortcut action in config: {}", name);
+            }
+        }
+
+        let mut bindings = Vec::new();
+        for action in Action::ALL {
+            let keys: Vec<String> = match overrides.get(action.config_name()) {
+                Some(keys) => keys.clone(),
+                None => action.default_keys().iter().map(|k| k.to_string()).collect(),
+            };
+
+            for label in keys {
+                let sequence: Option<Vec<KeyChord>> =
+                    label.split_whitespace().map(KeyChord::parse).collect();
+                match sequence {
+                    Some(sequence) if (1..=2).contains(&sequence.len()) => bindings.push(Binding {
+                        sequence,
+                        label,
+                        action,
+                    }),
+                    _ => log::warn!("Invalid shortcut for {}: {:?}", action.config_name(), label),
+                }
+            }
+        }
+
+        Self {
+            bindings,
+            pending: None,
+        }
+    }
+
+    /// Key labels bound to an action, for display
+    pub fn keys_for(&self, action: Action) -> Vec<&str> {
+        self.bindings
+            .iter()
+            .filter(|b| b.action == action)
+            .map(|b| b.label.as_str())
+            .collect()
+    }
+
+    /// Collect the actions triggered by this frame's key presses.
+    /// While a text field has focus only Ctrl/Cmd chords fire.
+    pub fn poll(&mut self, ctx: &egui::Context) -> Vec<Action> {
+        let now = ctx.input(|i| i.time);
+        if let Some((_, started)) = self.pending {
+            if now - started > SEQUENCE_TIMEOUT {
+                self.pending = None;
+            }
+        }
+
+        let typing = ctx.wants_keyboard_input();
+        let presses: Vec<(usize, egui::Key, egui::Modifiers)> = ctx.input(|i| {
+            i.events
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, event)| match event {
+                    egui::Event::Key {
+                        key,
+                        pressed: true,
+                        repeat: false,
+                        modifiers,
+                        ..
+                    } => Some((idx, *key, *modifiers)),
+                    _ => None,
+                })
+                .collect()
+        });
+
+        let mut actions = Vec::new();
+        let mut handled = Vec::new();
+        for (idx, key, modifiers) in presses {
+            if typing && !(modifiers.ctrl || modifiers.command) {
+                continue;
+            }
+            let action = self.press(key, modifiers, now);
+            // A sequence left pending was started by this key
+            if action.is_some() || self.pending.is_some() {
+                handled.push(idx);
+            }
+            actions.extend(action);
+        }
+
+        // Don't let a shortcut's character leak into a field focused by it
+        if !handled.is_empty() {
+            ctx.input_mut(|i| remove_typed_text(&mut i.events, &handled));
+        }
+
+        actions
+    }
+
+    fn press(&mut self, key: egui::Key, modifiers: egui::Modifiers, now: f64) -> Option<Action> {
+        // Complete a pending sequence
+        if let Some((first, _)) = self.pending.take() {
+            let completed = self.bindings.iter().find(|b| {
+                b.sequence.len() == 2 && b.sequence[0] == first && b.sequence[1].matches(key, modifiers)
+            });
+            if let Some(binding) = completed {
+                return Some(binding.action);
+            }
+        }
+
+        // Start a new sequence
+        if let Some(binding) = self
+            .bindings
+            .iter()
+            .find(|b| b.sequence.len() == 2 && b.sequence[0].matches(key, modifiers))
+        {
+            self.pending = Some((binding.sequence[0], now));
+            return None;
+        }
+
+        self.bindings
+            .iter()
+            .find(|b| b.sequence.len() == 1 && b.sequence[0].matches(key, modifiers))
+            .map(|b| b.action)
+    }
+}
+
+/// Drop the text typed by each of the key presses at `keys`, leaving text
+/// from other keys in the same frame alone
+fn remove_typed_text(events: &mut Vec<egui::Event>, keys: &[usize]) {
+    let mut from_handled_key = false;
+    let mut idx = 0;
+    events.retain(|event| {
+        let keep = match event {
+            egui::Event::Key { pressed: true, .. } => {
+                from_handled_key = keys.contains(&idx);
+                true
+            }
+            egui::Event::Text(_) => !std::mem::take(&mut from_handled_key),
+            _ => true,
+        };
+        idx += 1;
+        keep
+    });
+}
+
+pub struct ShortcutsOverlay;
+
+impl ShortcutsOverlay {
+    pub fn render(ctx: &egui::Context, i18n: &I18n, keymap: &Keymap, open: &mut bool) {
+        if !*open {
+            return;
+        }
+
+        let modal = egui::Modal::new(egui::Id::new("shortcuts_overlay")).show(ctx, |ui| {
+            ui.heading(i18n.tr("shortcuts-title"));
+            ui.add_space(5.0);
+            ui.separator();
+            ui.add_space(10.0);
+
+            egui::Grid::new("shortcuts_grid")
+                .num_columns(2)
+                .spacing([20.0, 8.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for action in Action::ALL {
+                        ui.label(action.description(i18n));
+                        ui.horizontal(|ui| {
+                            for key in keymap.keys_for(action) {
+                                ui.code(key);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(10.0);
+            ui.label(
+                egui::RichText::new(i18n.tr("shortcuts-remap-hint"))
+                    .small()
+                    .weak(),
+            );
+        });
+
+        if modal.should_close() {
+            *open = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{Key, Modifiers};
+
+    fn chord(s: &str) -> KeyChord {
+        KeyChord::parse(s).unwrap_or_else(|| panic!("{s:?} doesn't parse"))
+    }
+
+    fn key_event(key: Key, modifiers: Modifiers) -> egui::Event {
+        egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers,
+        }
+    }
+
+    #[test]
+    fn chords_parse_keys_and_modifiers() {
+        assert_eq!(chord("ctrl+k"), KeyChord { key: Key::K, modifiers: Modifiers::CTRL });
+        assert_eq!(chord("Cmd+Shift+P").modifiers, Modifiers::COMMAND | Modifiers::SHIFT);
+        assert_eq!(chord("escape").key, Key::Escape);
+        assert_eq!(chord("?").key, Key::Questionmark);
+        assert_eq!(chord("F5").key, Key::F5);
+        assert_eq!(KeyChord::parse("hyper+k"), None);
+        assert_eq!(KeyChord::parse("ctrl+nokey"), None);
+    }
+
+    #[test]
+    fn ctrl_and_cmd_follow_the_platform() {
+        let mac_cmd = Modifiers::MAC_CMD | Modifiers::COMMAND;
+        // On macOS, Ctrl and Cmd are different keys
+        assert!(chord("ctrl+k").matches(Key::K, Modifiers::CTRL));
+        assert!(!chord("ctrl+k").matches(Key::K, mac_cmd));
+        assert!(chord("cmd+k").matches(Key::K, mac_cmd));
+        assert!(!chord("cmd+k").matches(Key::K, Modifiers::CTRL));
+        // Elsewhere egui reports Ctrl as the command key too
+        let ctrl = Modifiers::CTRL | Modifiers::COMMAND;
+        assert!(chord("ctrl+k").matches(Key::K, ctrl));
+        assert!(chord("cmd+k").matches(Key::K, ctrl));
+
+        assert!(!chord("k").matches(Key::K, ctrl));
+        assert!(chord("?").matches(Key::Questionmark, Modifiers::SHIFT));
+        assert!(!chord("shift+k").matches(Key::K, Modifiers::NONE));
+        assert!(!chord("k").matches(Key::K, Modifiers::ALT));
+    }
+
+    #[test]
+    fn sequences_complete_and_other_keys_drop_them() {
+        let mut keymap = Keymap::from_config(&BTreeMap::new());
+        assert_eq!(keymap.press(Key::G, Modifiers::NONE, 0.0), None);
+        assert_eq!(keymap.press(Key::H, Modifiers::NONE, 0.5), Some(Action::GoHome));
+
+        // Another key drops the pending sequence and is handled on its own
+        assert_eq!(keymap.press(Key::G, Modifiers::NONE, 1.0), None);
+        assert_eq!(keymap.press(Key::M, Modifiers::NONE, 1.2), Some(Action::ToggleSidebar));
+        assert_eq!(keymap.press(Key::H, Modifiers::NONE, 1.3), None);
+
+        let overrides = [("go_home".to_string(), vec!["ctrl+h".to_string(), "not a key".to_string()])].into();
+        let mut keymap = Keymap::from_config(&overrides);
+        assert_eq!(keymap.keys_for(Action::GoHome), ["ctrl+h"]);
+        assert_eq!(keymap.press(Key::H, Modifiers::CTRL, 0.0), Some(Action::GoHome));
+        assert_eq!(keymap.press(Key::Num1, Modifiers::NONE, 0.0), None);
+    }
+
+    /// Actions from a frame at `time` in which `key` is pressed
+    fn poll_at(keymap: &mut Keymap, ctx: &egui::Context, time: f64, key: Option<Key>) -> Vec<Action> {
+        let input = egui::RawInput {
+            time: Some(time),
+            events: key.map(|key| key_event(key, Modifiers::NONE)).into_iter().collect(),
+            ..Default::default()
+        };
+        let mut actions = Vec::new();
+        let _ = ctx.run(input, |ctx| actions = keymap.poll(ctx));
+        actions
+    }
+
+    #[test]
+    fn sequences_expire_after_the_timeout() {
+        let ctx = egui::Context::default();
+        let mut keymap = Keymap::from_config(&BTreeMap::new());
+
+        assert!(poll_at(&mut keymap, &ctx, 0.0, Some(Key::G)).is_empty());
+        assert_eq!(poll_at(&mut keymap, &ctx, SEQUENCE_TIMEOUT - 0.1, Some(Key::H)), [Action::GoHome]);
+
+        // Too late: the G is forgotten and H alone does nothing
+        let start = 10.0;
+        assert!(poll_at(&mut keymap, &ctx, start, Some(Key::G)).is_empty());
+        assert!(poll_at(&mut keymap, &ctx, start + SEQUENCE_TIMEOUT + 0.1, Some(Key::H)).is_empty());
+        assert!(keymap.pending.is_none());
+
+        // A frame without keys drops it as well
+        assert!(poll_at(&mut keymap, &ctx, 20.0, Some(Key::G)).is_empty());
+        assert!(poll_at(&mut keymap, &ctx, 20.0 + SEQUENCE_TIMEOUT + 0.1, None).is_empty());
+        assert!(keymap.pending.is_none());
+    }
+
+    #[test]
+    fn only_the_shortcuts_own_text_is_removed() {
+        let mut events = vec![
+            egui::Event::Text("a".to_string()),
+            key_event(Key::Slash, Modifiers::NONE),
+            egui::Event::Text("/".to_string()),
+            key_event(Key::B, Modifiers::NONE),
+            egui::Event::Text("b".to_string()),
+        ];
+        remove_typed_text(&mut events, &[1]);
+        let text: Vec<&str> = events
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, ["a", "b"]);
+    }
+}
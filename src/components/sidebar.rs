@@ -1,90 +1,60 @@
-use eframe::egui;
-use std::sync::mpsc;
+use eframe::egui::{self, accesskit::Role};
 
+use crate::components::a11y;
 use crate::config::Config;
+use crate::i18n::I18n;
 use crate::routes::Route;
 
-pub struct Sidebar;
-
-impl Sidebar {
-    pub fn render(
-        ui: &mut egui::Ui,
-        ctx: &egui::Context,
-        config: &Config,
-        current_route: &mut Route,
-        artworks_count: usize,
-        resume_content: &Option<String>,
-        resume_loading: &mut bool,
-        resume_receiver: &mut Option<mpsc::Receiver<ehttp::Result<ehttp::Response>>>,
-        settings_open: &mut bool,
-    ) {
-        // Home route
-        let home_selected = *current_route == Route::Home;
-        if ui.selectable_label(home_selected, "Home").clicked() {
-            *current_route = Route::Home;
-            current_route.update_browser_url();
-        }
-
-        ui.add_space(5.0);
-
-        // Artworks route
-        let artworks_selected = *current_route == Route::Artworks;
-        if ui.selectable_label(artworks_selected, "Artworks").clicked() {
-            *current_route = Route::Artworks;
-            current_route.update_browser_url();
-        }
-
-        ui.add_space(5.0);
-
-        // Resume route
-        let resume_selected = *current_route == Route::Resume;
-        ui.horizontal(|ui| {
-            if ui.selectable_label(resume_selected, "Resume").clicked() {
-                if resume_content.is_none() && !*resume_loading {
-                    // Fetch resume if not already loaded
-                    *resume_loading = true;
-                    let ctx = ctx.clone();
-                    let (sender, receiver) = mpsc::channel();
-                    *resume_receiver = Some(receiver);
-
-                    let resume_url = config.app.resume.clone();
-                    ehttp::fetch(
-                        ehttp::Request::get(&resume_url),
-                        move |result| {
-                            let _ = sender.send(result);
-                            ctx.request_repaint();
-                        },
-                    );
-                } else if resume_content.is_some() {
-                    *current_route = Route::Resume;
-                }
-                current_route.update_browser_url();
-            }
+/// Navigation menu with the route list, artwork count, settings button and footer
+pub struct Sidebar<'a> {
+    pub config: &'a Config,
+    pub i18n: &'a I18n,
+    pub current_route: Route,
+    pub artworks_count: usize,
+    pub resume_loading: bool,
+    pub settings_open: &'a mut bool,
+}
 
-            if *resume_loading {
-                ui.spinner();
+impl Sidebar<'_> {
+    /// Returns the route to navigate to if one was clicked
+    pub fn render(self, ui: &mut egui::Ui) -> Option<Route> {
+        let Self { config, i18n, current_route, artworks_count, resume_loading, settings_open } = self;
+        let mut clicked = None;
+
+        a11y::set_role(ui, Role::Navigation, Some(i18n.label("sidebar-label")));
+
+        for route in Route::all(config) {
+            let selected = current_route == route;
+            let title = route.title(config, i18n);
+
+            if route == Route::Resume {
+                ui.horizontal(|ui| {
+                    if ui.selectable_label(selected, title).clicked() {
+                        clicked = Some(route);
+                    }
+
+                    if resume_loading {
+                        ui.spinner();
+                    }
+                });
+            } else if ui.selectable_label(selected, title).clicked() {
+                clicked = Some(route);
             }
-        });
-
-        ui.add_space(5.0);
 
-        // About route
-        let about_selected = *current_route == Route::About;
-        if ui.selectable_label(about_selected, "About").clicked() {
-            *current_route = Route::About;
-            current_route.update_browser_url();
+            ui.add_space(5.0);
         }
 
         ui.add_space(10.0);
         ui.separator();
         ui.add_space(10.0);
 
-        ui.label(format!("Artworks loaded: {}", artworks_count));
+        ui.label(i18n.tr_args("sidebar-artworks-loaded", &[("count", &artworks_count)]));
 
         ui.add_space(10.0);
 
         // Settings button and footer
-        ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+        let align = if i18n.is_rtl() { egui::Align::RIGHT } else { egui::Align::LEFT };
+        ui.with_layout(egui::Layout::bottom_up(align), |ui| {
             ui.add_space(10.0);
             ui.horizontal(|ui| {
                 // Get current year dynamically
@@ -104,11 +74,19 @@ impl Sidebar {
                 };
 
                 ui.label(format!("© {}", year));
-                ui.hyperlink_to(&config.app.name, &config.app.website);
+                if config.links.is_empty() {
+                    ui.hyperlink_to(&config.app.name, &config.app.website);
+                }
+                for link in &config.links {
+                    let icon = ui.hyperlink_to(link.icon(), &link.url).on_hover_text(&link.label);
+                    a11y::set_label(&icon, &link.label);
+                }
             });
             ui.add_space(10.0);
 
-            if ui.button("⚙ Settings").clicked() {
+            let settings_button = ui.button(i18n.tr("settings-button"));
+            a11y::set_label(&settings_button, i18n.label("settings-label"));
+            if settings_button.clicked() {
                 *settings_open = true;
             }
 
@@ -116,5 +94,7 @@ impl Sidebar {
             ui.separator();
             ui.add_space(10.0);
         });
+
+        clicked
     }
 }
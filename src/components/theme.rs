@@ -1,10 +1,14 @@
 use eframe::egui;
 
+use crate::components::Typography;
+use crate::i18n::I18n;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ThemeMode {
     Auto,
     Dark,
     Light,
+    HighContrast,
 }
 
 impl ThemeMode {
@@ -12,6 +16,7 @@ impl ThemeMode {
         match s.to_lowercase().as_str() {
             "dark" => ThemeMode::Dark,
             "light" => ThemeMode::Light,
+            "high_contrast" | "high-contrast" | "contrast" => ThemeMode::HighContrast,
             _ => ThemeMode::Auto,
         }
     }
@@ -20,21 +25,22 @@ impl ThemeMode {
         match self {
             ThemeMode::Light => ctx.set_visuals(Self::custom_light()),
             ThemeMode::Dark => ctx.set_visuals(Self::custom_dark()),
+            ThemeMode::HighContrast => ctx.set_visuals(Self::high_contrast()),
             ThemeMode::Auto => {
                 // Use system preference if available, otherwise default to dark
                 #[cfg(target_arch = "wasm32")]
                 {
-                    if let Some(window) = web_sys::window() {
-                        if let Ok(media_query) = window.match_media("(prefers-color-scheme: dark)") {
-                            if let Some(mq) = media_query {
-                                if mq.matches() {
-                                    ctx.set_visuals(Self::custom_dark());
-                                } else {
-                                    ctx.set_visuals(Self::custom_light());
-                                }
-                                return;
-                            }
+                    if media_query_matches("(prefers-contrast: more)") == Some(true) {
+                        ctx.set_visuals(Self::high_contrast());
+                        return;
+                    }
+                    if let Some(dark) = media_query_matches("(prefers-color-scheme: dark)") {
+                        if dark {
+                            ctx.set_visuals(Self::custom_dark());
+                        } else {
+                            ctx.set_visuals(Self::custom_light());
                         }
+                        return;
                     }
                 }
                 #[cfg(not(target_arch = "wasm32"))]
@@ -55,41 +61,18 @@ impl ThemeMode {
         visuals.window_fill = egui::Color32::from_rgb(235, 242, 250);
         visuals.extreme_bg_color = egui::Color32::from_rgb(200, 215, 235);
 
+        // Darker red so error text stays readable on the light blue panels
+        visuals.error_fg_color = egui::Color32::from_rgb(170, 0, 0);
+
         visuals
     }
 
-    pub fn apply_with_style(&self, ctx: &egui::Context) {
-        // Increase font sizes first
+    pub fn apply_with_style(&self, ctx: &egui::Context, typography: &Typography) {
+        // Apply font sizes and spacing first
         let mut style = (*ctx.style()).clone();
-
-        style.text_styles.insert(
-            egui::TextStyle::Body,
-            egui::FontId::proportional(18.0),
-        );
-        style.text_styles.insert(
-            egui::TextStyle::Button,
-            egui::FontId::proportional(18.0),
-        );
-        style.text_styles.insert(
-            egui::TextStyle::Heading,
-            egui::FontId::proportional(28.0),
-        );
-        style.text_styles.insert(
-            egui::TextStyle::Monospace,
-            egui::FontId::monospace(17.0),
-        );
-        style.text_styles.insert(
-            egui::TextStyle::Small,
-            egui::FontId::proportional(15.0),
-        );
-
-        // Improve vertical spacing for better rhythm
-        style.spacing.item_spacing.y = 10.0; // More space between items
-        style.spacing.window_margin = egui::Margin::same(12); // More padding
-        style.spacing.button_padding = egui::vec2(10.0, 5.0); // Better button padding
-        style.spacing.indent = 20.0; // Better indentation
-
+        typography.apply_to_style(&mut style);
         ctx.set_style(style);
+        typography.apply_zoom(ctx);
 
         // Apply colors after style
         self.apply(ctx);
@@ -103,14 +86,65 @@ impl ThemeMode {
         visuals.window_fill = egui::Color32::from_rgb(20, 25, 40);
         visuals.extreme_bg_color = egui::Color32::from_rgb(10, 15, 28);
 
+        visuals.error_fg_color = egui::Color32::from_rgb(255, 110, 110);
+
         visuals
     }
 
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            ThemeMode::Auto => "Auto",
-            ThemeMode::Light => "Light",
-            ThemeMode::Dark => "Dark",
+    /// Black background with white text and yellow links.
+    /// Every text color here has at least a 7:1 contrast ratio against black,
+    /// well above the WCAG AA requirement of 4.5:1.
+    fn high_contrast() -> egui::Visuals {
+        let mut visuals = egui::Visuals::dark();
+        let white = egui::Color32::WHITE;
+        let black = egui::Color32::BLACK;
+
+        visuals.panel_fill = black;
+        visuals.window_fill = black;
+        visuals.extreme_bg_color = black;
+        visuals.faint_bg_color = egui::Color32::from_gray(24);
+        visuals.code_bg_color = egui::Color32::from_gray(24);
+        visuals.window_stroke = egui::Stroke::new(2.0, white);
+
+        visuals.hyperlink_color = egui::Color32::from_rgb(255, 255, 0);
+        visuals.error_fg_color = egui::Color32::from_rgb(255, 128, 128);
+        visuals.warn_fg_color = egui::Color32::from_rgb(255, 200, 0);
+        visuals.weak_text_color = Some(egui::Color32::from_gray(200));
+
+        visuals.selection.bg_fill = egui::Color32::from_rgb(0, 80, 160);
+        visuals.selection.stroke = egui::Stroke::new(2.0, white);
+
+        let widgets = &mut visuals.widgets;
+        widgets.noninteractive.fg_stroke = egui::Stroke::new(1.0, white);
+        widgets.noninteractive.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_gray(200));
+        for state in [&mut widgets.inactive, &mut widgets.hovered, &mut widgets.active, &mut widgets.open] {
+            state.fg_stroke = egui::Stroke::new(1.5, white);
+            state.bg_fill = black;
+            state.weak_bg_fill = black;
+            state.bg_stroke = egui::Stroke::new(1.5, white);
         }
+        widgets.hovered.bg_fill = egui::Color32::from_gray(40);
+        widgets.hovered.weak_bg_fill = egui::Color32::from_gray(40);
+        widgets.hovered.bg_stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 255, 0));
+        widgets.active.bg_stroke = egui::Stroke::new(2.5, egui::Color32::from_rgb(255, 255, 0));
+
+        visuals
     }
+
+    pub fn label<'a>(&self, i18n: &'a I18n) -> &'a str {
+        i18n.tr(match self {
+            ThemeMode::Auto => "theme-auto",
+            ThemeMode::Light => "theme-light",
+            ThemeMode::Dark => "theme-dark",
+            ThemeMode::HighContrast => "theme-high-contrast",
+        })
+    }
+}
+
+/// Evaluate a CSS media query, `None` if the browser can't answer
+#[cfg(target_arch = "wasm32")]
+pub fn media_query_matches(query: &str) -> Option<bool> {
+    let window = web_sys::window()?;
+    let media_query = window.match_media(query).ok()??;
+    Some(media_query.matches())
 }
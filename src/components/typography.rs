@@ -0,0 +1,148 @@
+use eframe::egui;
+use egui::gui_zoom::kb_shortcuts;
+use serde::{Deserialize, Serialize};
+
+use crate::config::TypographyConfig;
+
+const MIN_FONT_SIZE: f32 = 10.0;
+const MAX_FONT_SIZE: f32 = 40.0;
+const MIN_UI_ZOOM: f32 = 0.5;
+const MAX_UI_ZOOM: f32 = 3.0;
+const MIN_LINE_SPACING: f32 = 0.0;
+const MAX_LINE_SPACING: f32 = 30.0;
+const ZOOM_STEP: f32 = 0.1;
+
+/// User-adjustable text size, UI zoom and line spacing
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Typography {
+    pub font_size: f32,
+    pub ui_zoom: f32,
+    pub line_spacing: f32,
+}
+
+impl Typography {
+    pub const STORAGE_KEY: &'static str = "typography";
+
+    pub fn from_config(config: &TypographyConfig) -> Self {
+        Self {
+            font_size: config.font_size,
+            ui_zoom: config.ui_zoom,
+            line_spacing: config.line_spacing,
+        }
+        .clamped()
+    }
+
+    /// Keep values within sensible bounds (e.g. after loading from storage)
+    pub fn clamped(self) -> Self {
+        Self {
+            font_size: self.font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE),
+            ui_zoom: self.ui_zoom.clamp(MIN_UI_ZOOM, MAX_UI_ZOOM),
+            line_spacing: self.line_spacing.clamp(MIN_LINE_SPACING, MAX_LINE_SPACING),
+        }
+    }
+
+    pub fn font_size_range() -> std::ops::RangeInclusive<f32> {
+        MIN_FONT_SIZE..=MAX_FONT_SIZE
+    }
+
+    pub fn ui_zoom_range() -> std::ops::RangeInclusive<f32> {
+        MIN_UI_ZOOM..=MAX_UI_ZOOM
+    }
+
+    pub fn line_spacing_range() -> std::ops::RangeInclusive<f32> {
+        MIN_LINE_SPACING..=MAX_LINE_SPACING
+    }
+
+    /// Apply font sizes and spacing to the given style.
+    /// The other text styles keep the proportions of the original 18px design.
+    pub fn apply_to_style(&self, style: &mut egui::Style) {
+        let base = self.font_size;
+
+        style.text_styles.insert(
+            egui::TextStyle::Body,
+            egui::FontId::proportional(base),
+        );
+        style.text_styles.insert(
+            egui::TextStyle::Button,
+            egui::FontId::proportional(base),
+        );
+        style.text_styles.insert(
+            egui::TextStyle::Heading,
+            egui::FontId::proportional(base * 28.0 / 18.0),
+        );
+        style.text_styles.insert(
+            egui::TextStyle::Monospace,
+            egui::FontId::monospace(base * 17.0 / 18.0),
+        );
+        style.text_styles.insert(
+            egui::TextStyle::Small,
+            egui::FontId::proportional(base * 15.0 / 18.0),
+        );
+
+        // Vertical rhythm follows the configured line spacing
+        style.spacing.item_spacing.y = self.line_spacing;
+        style.spacing.window_margin = egui::Margin::same(12); // More padding
+        style.spacing.button_padding = egui::vec2(10.0, 5.0); // Better button padding
+        style.spacing.indent = 20.0; // Better indentation
+    }
+
+    /// Apply the UI zoom, only touching the context when it actually changed
+    pub fn apply_zoom(&self, ctx: &egui::Context) {
+        if (ctx.zoom_factor() - self.ui_zoom).abs() > f32::EPSILON {
+            ctx.set_zoom_factor(self.ui_zoom);
+        }
+    }
+
+    /// Handle Ctrl+Plus / Ctrl+Minus / Ctrl+0, which only change the UI zoom
+    pub fn handle_shortcuts(&mut self, ctx: &egui::Context, defaults: &Typography) {
+        if ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_RESET)) {
+            self.ui_zoom = defaults.ui_zoom;
+        } else if ctx.input_mut(|i| {
+            i.consume_shortcut(&kb_shortcuts::ZOOM_IN)
+                || i.consume_shortcut(&kb_shortcuts::ZOOM_IN_SECONDARY)
+        }) {
+            self.ui_zoom = Self::round_zoom(self.ui_zoom + ZOOM_STEP);
+        } else if ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_OUT)) {
+            self.ui_zoom = Self::round_zoom(self.ui_zoom - ZOOM_STEP);
+        }
+
+        *self = self.clamped();
+    }
+
+    fn round_zoom(zoom: f32) -> f32 {
+        (zoom * 10.0).round() / 10.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(typography: &mut Typography, defaults: &Typography, key: egui::Key) {
+        let ctx = egui::Context::default();
+        let input = egui::RawInput {
+            events: vec![egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: egui::Modifiers::COMMAND,
+            }],
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| typography.handle_shortcuts(ctx, defaults));
+    }
+
+    #[test]
+    fn zoom_reset_only_restores_the_zoom() {
+        let defaults = Typography { font_size: 18.0, ui_zoom: 1.0, line_spacing: 8.0 };
+        let mut typography = Typography { font_size: 24.0, ui_zoom: 1.0, line_spacing: 12.0 };
+
+        press(&mut typography, &defaults, egui::Key::Plus);
+        press(&mut typography, &defaults, egui::Key::Plus);
+        assert_eq!(typography.ui_zoom, 1.2);
+
+        press(&mut typography, &defaults, egui::Key::Num0);
+        assert_eq!(typography, Typography { font_size: 24.0, ui_zoom: 1.0, line_spacing: 12.0 });
+    }
+}
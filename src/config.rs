@@ -1,4 +1,7 @@
 use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::routes::Route;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct AppConfig {
@@ -9,21 +12,269 @@ pub struct AppConfig {
     pub repository: String,
     #[serde(default = "default_theme")]
     pub default_theme: String,
+    #[serde(default = "default_motion")]
+    pub default_motion: String,
+    #[serde(default = "default_resume_format")]
+    pub resume_format: String,
+    /// Locale code like "fr", or "auto" to follow the browser
+    #[serde(default = "default_locale")]
+    pub default_locale: String,
 }
 
 fn default_theme() -> String {
     "auto".to_string()
 }
 
+fn default_motion() -> String {
+    "auto".to_string()
+}
+
+fn default_resume_format() -> String {
+    "html".to_string()
+}
+
+fn default_locale() -> String {
+    "auto".to_string()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TypographyConfig {
+    #[serde(default = "default_font_size")]
+    pub font_size: f32,
+    #[serde(default = "default_ui_zoom")]
+    pub ui_zoom: f32,
+    #[serde(default = "default_line_spacing")]
+    pub line_spacing: f32,
+}
+
+fn default_font_size() -> f32 {
+    18.0
+}
+
+fn default_ui_zoom() -> f32 {
+    1.0
+}
+
+fn default_line_spacing() -> f32 {
+    10.0
+}
+
+impl Default for TypographyConfig {
+    fn default() -> Self {
+        Self {
+            font_size: default_font_size(),
+            ui_zoom: default_ui_zoom(),
+            line_spacing: default_line_spacing(),
+        }
+    }
+}
+
+/// A font file to load at startup.
+/// `source` is fetched over HTTP on web; on native it is read from disk
+/// unless it is an http(s) URL.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FontFile {
+    pub name: String,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FontsConfig {
+    /// Font names tried in order for proportional text, before egui's defaults
+    #[serde(default)]
+    pub proportional: Vec<String>,
+    /// Font names tried in order for monospace text, before egui's defaults
+    #[serde(default)]
+    pub monospace: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<FontFile>,
+}
+
+/// A markdown page from `[[pages]]`, routed as `#/<slug>`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PageConfig {
+    pub slug: String,
+    pub title: String,
+    /// Sidebar order; Artworks sits at 20 and Resume at 30
+    #[serde(default)]
+    pub position: i32,
+    /// Page content; takes precedence over `url`
+    #[serde(default)]
+    pub markdown: Option<String>,
+    /// Where to fetch the page's markdown from
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Posts listed from a feed, routed as `#/blog` and `#/blog/<slug>`
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlogConfig {
+    /// A JSON list of posts or an Atom feed
+    pub index: String,
+    /// Title in the menu and above the list; the catalog's "Blog" when unset
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Sidebar order; Artworks sits at 20 and Resume at 30
+    #[serde(default = "default_blog_position")]
+    pub position: i32,
+    /// Posts per page of the list
+    #[serde(default = "default_posts_per_page")]
+    pub per_page: usize,
+}
+
+fn default_blog_position() -> i32 {
+    35
+}
+
+fn default_posts_per_page() -> usize {
+    10
+}
+
+/// An entry of `[[links]]`, shown on the Links page and in the sidebar footer
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinkConfig {
+    pub label: String,
+    pub url: String,
+    /// A glyph from egui's fonts, e.g. "🌐"; the label's first letter when empty
+    #[serde(default)]
+    pub icon: String,
+    /// Heading the link is listed under on the Links page
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+impl LinkConfig {
+    pub fn icon(&self) -> String {
+        if self.icon.is_empty() {
+            self.label.chars().take(1).collect()
+        } else {
+            self.icon.clone()
+        }
+    }
+}
+
+/// Translated content from `[locales.<code>]`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LocaleConfig {
+    /// Lay the UI out right to left; by default only for languages such as Arabic and Hebrew
+    #[serde(default)]
+    pub rtl: Option<bool>,
+    /// Messages that replace or add to the built-in catalog, by key
+    #[serde(default)]
+    pub messages: BTreeMap<String, String>,
+    /// Translated `[[pages]]`, by slug
+    #[serde(default)]
+    pub pages: BTreeMap<String, PageTranslation>,
+    /// Translated artwork info, by artwork name
+    #[serde(default)]
+    pub artworks: BTreeMap<String, String>,
+}
+
+/// A page's title and content in another locale; unset fields keep the original
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PageTranslation {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub markdown: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// HTML sanitization policy for fetched documents
+#[derive(Debug, Clone, Deserialize)]
+pub struct SanitizeConfig {
+    /// Tags kept in HTML; ammonia's default allowlist when unset.
+    /// `script` and `style` are always removed along with their content.
+    #[serde(default)]
+    pub allowed_tags: Option<Vec<String>>,
+    /// Schemes allowed in links and image sources; relative URLs are always allowed
+    #[serde(default = "default_url_schemes")]
+    pub url_schemes: Vec<String>,
+    /// Resolve relative links against the document's own URL
+    #[serde(default = "default_rewrite_relative_links")]
+    pub rewrite_relative_links: bool,
+}
+
+fn default_url_schemes() -> Vec<String> {
+    ["http", "https", "mailto", "tel"].map(String::from).to_vec()
+}
+
+fn default_rewrite_relative_links() -> bool {
+    true
+}
+
+impl Default for SanitizeConfig {
+    fn default() -> Self {
+        Self {
+            allowed_tags: None,
+            url_schemes: default_url_schemes(),
+            rewrite_relative_links: default_rewrite_relative_links(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub app: AppConfig,
+    #[serde(default)]
+    pub typography: TypographyConfig,
+    #[serde(default)]
+    pub fonts: FontsConfig,
+    #[serde(default)]
+    pub sanitize: SanitizeConfig,
+    #[serde(default)]
+    pub pages: Vec<PageConfig>,
+    #[serde(default)]
+    pub blog: Option<BlogConfig>,
+    #[serde(default)]
+    pub links: Vec<LinkConfig>,
+    /// Per-locale content, by locale code
+    #[serde(default)]
+    pub locales: BTreeMap<String, LocaleConfig>,
+    /// Keyboard shortcut overrides, action name -> keys
+    #[serde(default)]
+    pub shortcuts: BTreeMap<String, Vec<String>>,
 }
 
 impl Config {
     pub fn load() -> Self {
         const CONFIG_TOML: &str = include_str!("../config.toml");
-        toml::from_str(CONFIG_TOML).expect("Failed to parse config.toml")
+        let config: Self = toml::from_str(CONFIG_TOML).expect("Failed to parse config.toml");
+        config.prepared()
+    }
+
+    /// A config file other than the built-in one
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_file(path: &std::path::Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let config: Self = toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(config.prepared())
+    }
+
+    /// Fill in `{name}` and `{website}` in page markdown, and warn about
+    /// pages that a built-in page hides
+    fn prepared(mut self) -> Self {
+        let fill = |markdown: &mut Option<String>| {
+            if let Some(markdown) = markdown {
+                *markdown = markdown
+                    .replace("{name}", &self.app.name)
+                    .replace("{website}", &self.app.website);
+            }
+        };
+        for page in &mut self.pages {
+            fill(&mut page.markdown);
+        }
+        for page in self.locales.values_mut().flat_map(|locale| locale.pages.values_mut()) {
+            fill(&mut page.markdown);
+        }
+
+        for page in &self.pages {
+            if !matches!(Route::from_slug(&page.slug, &self), Some(Route::Page(_))) {
+                log::warn!("Page {:?} is hidden by the built-in page with that slug", page.slug);
+            }
+        }
+        self
     }
 }
 
@@ -32,3 +283,17 @@ impl Default for Config {
         Self::load()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_markdown_gets_the_app_name_and_website() {
+        let config = Config::load();
+        let home = config.pages.iter().find(|page| page.slug == "home").unwrap();
+        let markdown = home.markdown.as_deref().unwrap();
+        assert!(markdown.contains(&format!("<{}>", config.app.website)), "{markdown}");
+        assert!(!markdown.contains("{website}"));
+    }
+}
@@ -0,0 +1,510 @@
+//! HTTP fetching behind a trait, so tests and offline demos can answer
+//! requests from memory, recordings or local files instead of the network.
+
+use eframe::egui;
+use egui::load::{Bytes, BytesLoadResult, BytesLoader, BytesPoll, LoadError};
+use egui::mutex::Mutex;
+use std::collections::HashMap;
+use std::sync::{mpsc, Arc};
+use std::task::Poll;
+
+pub type FetchResult = ehttp::Result<ehttp::Response>;
+
+/// Fetches a URL and hands the result to a callback, possibly on another thread
+pub trait Fetch: Send + Sync {
+    fn fetch(&self, url: &str, on_done: Box<dyn FnOnce(FetchResult) + Send>);
+}
+
+impl dyn Fetch + '_ {
+    /// Start fetching `url`; the result arrives on the receiver, and `ctx`,
+    /// when given, is repainted so it gets picked up
+    pub fn receive(&self, url: &str, ctx: Option<&egui::Context>) -> mpsc::Receiver<FetchResult> {
+        let ctx = ctx.cloned();
+        let (sender, receiver) = mpsc::channel();
+        self.fetch(
+            url,
+            Box::new(move |result| {
+                let _ = sender.send(result);
+                if let Some(ctx) = ctx {
+                    ctx.request_repaint();
+                }
+            }),
+        );
+        receiver
+    }
+}
+
+/// The network, through `ehttp`
+pub struct HttpFetch;
+
+impl Fetch for HttpFetch {
+    fn fetch(&self, url: &str, on_done: Box<dyn FnOnce(FetchResult) + Send>) {
+        ehttp::fetch(ehttp::Request::get(url), on_done);
+    }
+}
+
+/// Image bytes for egui's image loaders, fetched through a `Fetch` so images
+/// are recorded and replayed along with everything else
+pub struct FetchLoader {
+    fetcher: Arc<dyn Fetch>,
+    cache: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+/// An image's bytes, or why they couldn't be fetched, once they arrive
+type Entry = Poll<Result<Loaded, String>>;
+
+#[derive(Clone)]
+struct Loaded {
+    bytes: Arc<[u8]>,
+    mime: Option<String>,
+}
+
+impl FetchLoader {
+    pub const ID: &'static str = egui::generate_loader_id!(FetchLoader);
+
+    pub fn new(fetcher: Arc<dyn Fetch>) -> Self {
+        Self {
+            fetcher,
+            cache: Default::default(),
+        }
+    }
+}
+
+impl BytesLoader for FetchLoader {
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn load(&self, ctx: &egui::Context, uri: &str) -> BytesLoadResult {
+        if !["http://", "https://", "file://"].iter().any(|scheme| uri.starts_with(scheme)) {
+            return Err(LoadError::NotSupported);
+        }
+
+        let mut cache = self.cache.lock();
+        match cache.get(uri) {
+            Some(Poll::Ready(Ok(loaded))) => {
+                return Ok(BytesPoll::Ready {
+                    size: None,
+                    bytes: Bytes::Shared(loaded.bytes.clone()),
+                    mime: loaded.mime.clone(),
+                })
+            }
+            Some(Poll::Ready(Err(error))) => return Err(LoadError::Loading(error.clone())),
+            Some(Poll::Pending) => return Ok(BytesPoll::Pending { size: None }),
+            None => {}
+        }
+        cache.insert(uri.to_string(), Poll::Pending);
+        drop(cache);
+
+        let ctx = ctx.clone();
+        let cache = self.cache.clone();
+        let url = uri.to_string();
+        self.fetcher.fetch(
+            uri,
+            Box::new(move |result| {
+                let loaded = match result {
+                    Ok(response) if response.ok => Ok(Loaded {
+                        mime: response.content_type().map(str::to_string),
+                        bytes: response.bytes.into(),
+                    }),
+                    Ok(response) => Err(format!("{}: {} {}", url, response.status, response.status_text)),
+                    Err(error) => Err(format!("{}: {}", url, error)),
+                };
+                // Forgotten while loading; nothing is waiting for it any more
+                if let Some(entry) = cache.lock().get_mut(&url) {
+                    *entry = Poll::Ready(loaded);
+                }
+                ctx.request_repaint();
+            }),
+        );
+        Ok(BytesPoll::Pending { size: None })
+    }
+
+    fn forget(&self, uri: &str) {
+        self.cache.lock().remove(uri);
+    }
+
+    fn forget_all(&self) {
+        self.cache.lock().clear();
+    }
+
+    fn byte_size(&self) -> usize {
+        self.cache
+            .lock()
+            .values()
+            .map(|entry| match entry {
+                Poll::Ready(Ok(loaded)) => loaded.bytes.len(),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    fn has_pending(&self) -> bool {
+        self.cache.lock().values().any(Poll::is_pending)
+    }
+}
+
+#[cfg(test)]
+pub use fixture::FixtureFetch;
+
+#[cfg(test)]
+mod fixture {
+    use std::collections::HashMap;
+
+    use super::{Fetch, FetchResult};
+
+    /// Canned responses by URL, answered immediately; other URLs get a 404
+    #[derive(Default)]
+    pub struct FixtureFetch {
+        responses: HashMap<String, FetchResult>,
+    }
+
+    impl FixtureFetch {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Answer `url` with a 200 and `body`
+        pub fn with(self, url: &str, body: impl Into<Vec<u8>>) -> Self {
+            self.with_status(url, 200, body)
+        }
+
+        pub fn with_status(mut self, url: &str, status: u16, body: impl Into<Vec<u8>>) -> Self {
+            self.responses.insert(url.to_string(), Ok(response(url, status, body.into())));
+            self
+        }
+
+        /// Fail `url` with a connection error
+        pub fn with_error(mut self, url: &str, error: &str) -> Self {
+            self.responses.insert(url.to_string(), Err(error.to_string()));
+            self
+        }
+    }
+
+    impl Fetch for FixtureFetch {
+        fn fetch(&self, url: &str, on_done: Box<dyn FnOnce(FetchResult) + Send>) {
+            let result = self
+                .responses
+                .get(url)
+                .cloned()
+                .unwrap_or_else(|| Ok(response(url, 404, Vec::new())));
+            on_done(result);
+        }
+    }
+
+    fn response(url: &str, status: u16, bytes: Vec<u8>) -> ehttp::Response {
+        ehttp::Response {
+            url: url.to_string(),
+            ok: (200..300).contains(&status),
+            status,
+            status_text: status_text(status).to_string(),
+            headers: Default::default(),
+            bytes,
+        }
+    }
+
+    fn status_text(status: u16) -> &'static str {
+        match status {
+            200 => "OK",
+            404 => "Not Found",
+            500 => "Internal Server Error",
+            _ => "",
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use recorded::RecordedFetch;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod recorded {
+    use serde::{Deserialize, Serialize};
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use super::{Fetch, FetchResult};
+
+    /// Status line and headers of a recorded response; the body is stored
+    /// beside it as is
+    #[derive(Serialize, Deserialize)]
+    struct Recording {
+        url: String,
+        status: u16,
+        status_text: String,
+        headers: Vec<(String, String)>,
+    }
+
+    /// Responses saved to a folder as they are fetched, or played back from one
+    pub struct RecordedFetch {
+        dir: PathBuf,
+        /// Fetches and saves through this; replays when `None`
+        upstream: Option<Arc<dyn Fetch>>,
+    }
+
+    impl RecordedFetch {
+        /// Fetch through `upstream`, saving successful responses to `dir`
+        pub fn record(dir: impl Into<PathBuf>, upstream: Arc<dyn Fetch>) -> Self {
+            Self {
+                dir: dir.into(),
+                upstream: Some(upstream),
+            }
+        }
+
+        /// Answer from the responses saved in `dir`; anything else is an error
+        pub fn replay(dir: impl Into<PathBuf>) -> Self {
+            Self {
+                dir: dir.into(),
+                upstream: None,
+            }
+        }
+
+        fn load(dir: &Path, url: &str) -> FetchResult {
+            let (meta_path, body_path) = paths(dir, url);
+            let meta = std::fs::read_to_string(&meta_path).map_err(|_| format!("No recording of {}", url))?;
+            let recording: Recording =
+                serde_json::from_str(&meta).map_err(|e| format!("Bad recording {}: {}", meta_path.display(), e))?;
+            let bytes = std::fs::read(&body_path).map_err(|e| format!("{}: {}", body_path.display(), e))?;
+
+            Ok(ehttp::Response {
+                url: recording.url,
+                ok: (200..300).contains(&recording.status),
+                status: recording.status,
+                status_text: recording.status_text,
+                headers: ehttp::Headers {
+                    headers: recording.headers,
+                },
+                bytes,
+            })
+        }
+
+        fn save(dir: &Path, url: &str, response: &ehttp::Response) -> std::io::Result<()> {
+            let (meta_path, body_path) = paths(dir, url);
+            let recording = Recording {
+                url: response.url.clone(),
+                status: response.status,
+                status_text: response.status_text.clone(),
+                headers: response.headers.headers.clone(),
+            };
+            std::fs::create_dir_all(dir)?;
+            std::fs::write(meta_path, serde_json::to_string_pretty(&recording)?)?;
+            std::fs::write(body_path, &response.bytes)
+        }
+    }
+
+    impl Fetch for RecordedFetch {
+        fn fetch(&self, url: &str, on_done: Box<dyn FnOnce(FetchResult) + Send>) {
+            let Some(upstream) = &self.upstream else {
+                on_done(Self::load(&self.dir, url));
+                return;
+            };
+
+            let dir = self.dir.clone();
+            let key = url.to_string();
+            upstream.fetch(
+                url,
+                Box::new(move |result| {
+                    if let Ok(response) = &result {
+                        if let Err(e) = Self::save(&dir, &key, response) {
+                            log::warn!("Failed to record {}: {}", key, e);
+                        }
+                    }
+                    on_done(result);
+                }),
+            );
+        }
+    }
+
+    /// Metadata and body files for `url`: a readable slug of the URL plus a
+    /// hash, so distinct URLs never share a file
+    fn paths(dir: &Path, url: &str) -> (PathBuf, PathBuf) {
+        let slug: String = url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '_' })
+            .take(60)
+            .collect();
+        let name = format!("{}-{:016x}", slug, fnv1a(url));
+        (dir.join(format!("{}.json", name)), dir.join(format!("{}.body", name)))
+    }
+
+    /// Stable across builds, unlike `DefaultHasher`
+    fn fnv1a(text: &str) -> u64 {
+        text.bytes()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use local::{file_url, LocalFetch};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod local {
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use super::{Fetch, FetchResult};
+
+    /// `file://` URLs read from disk, anything else fetched through `upstream`.
+    /// A missing `index.json` is answered with its folder's subfolders, in the
+    /// shape of the artworks index.
+    pub struct LocalFetch {
+        upstream: Arc<dyn Fetch>,
+    }
+
+    impl LocalFetch {
+        pub fn new(upstream: Arc<dyn Fetch>) -> Self {
+            Self { upstream }
+        }
+
+        fn read(url: &str, path: &Path) -> FetchResult {
+            let bytes = match std::fs::read(path) {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => match path.file_name() {
+                    Some(name) if name == "index.json" && path.parent().is_some_and(Path::is_dir) => {
+                        listing(path.parent().unwrap_or(path)).map_err(|e| format!("{}: {}", path.display(), e))?
+                    }
+                    _ => return Ok(response(url, 404, "Not Found", Vec::new())),
+                },
+                Err(e) => return Err(format!("{}: {}", path.display(), e)),
+            };
+            Ok(response(url, 200, "OK", bytes))
+        }
+    }
+
+    impl Fetch for LocalFetch {
+        fn fetch(&self, url: &str, on_done: Box<dyn FnOnce(FetchResult) + Send>) {
+            match file_path(url) {
+                Some(path) => on_done(Self::read(url, &path)),
+                None => self.upstream.fetch(url, on_done),
+            }
+        }
+    }
+
+    /// `file://` URL of an absolute path
+    pub fn file_url(path: &Path) -> String {
+        let path = path.to_string_lossy().replace('\\', "/");
+        match path.starts_with('/') {
+            true => format!("file://{}", path),
+            false => format!("file:///{}", path),
+        }
+    }
+
+    fn file_path(url: &str) -> Option<PathBuf> {
+        let path = url.strip_prefix("file://")?;
+        // `file:///C:/...` on Windows
+        let path = match cfg!(windows) {
+            true => path.trim_start_matches('/'),
+            false => path,
+        };
+        Some(PathBuf::from(path))
+    }
+
+    /// Subfolders of `dir` by name, skipping hidden ones
+    fn listing(dir: &Path) -> std::io::Result<Vec<u8>> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| !name.starts_with('.'))
+            .collect();
+        names.sort();
+
+        let contents: Vec<serde_json::Value> = names.iter().map(|name| serde_json::json!({ "name": name })).collect();
+        let index = serde_json::json!([{ "type": "directory", "name": ".", "contents": contents }]);
+        Ok(serde_json::to_vec_pretty(&index)?)
+    }
+
+    fn response(url: &str, status: u16, status_text: &str, bytes: Vec<u8>) -> ehttp::Response {
+        ehttp::Response {
+            url: url.to_string(),
+            ok: status == 200,
+            status,
+            status_text: status_text.to_string(),
+            headers: Default::default(),
+            bytes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn fetch_now(fetcher: &dyn Fetch, url: &str) -> FetchResult {
+        fetcher.receive(url, None).recv().expect("Fetch answered")
+    }
+
+    #[test]
+    fn fixtures_answer_by_url() {
+        let fixtures = FixtureFetch::new()
+            .with("https://a.test/index.json", "[]")
+            .with_status("https://a.test/gone", 500, "oops")
+            .with_error("https://a.test/offline", "connection refused");
+
+        let ok = fetch_now(&fixtures, "https://a.test/index.json").unwrap();
+        assert!(ok.ok);
+        assert_eq!(ok.text(), Some("[]"));
+
+        let failed = fetch_now(&fixtures, "https://a.test/gone").unwrap();
+        assert_eq!((failed.ok, failed.status), (false, 500));
+
+        assert_eq!(fetch_now(&fixtures, "https://a.test/offline").unwrap_err(), "connection refused");
+        assert_eq!(fetch_now(&fixtures, "https://a.test/other").unwrap().status, 404);
+    }
+
+    #[test]
+    fn images_load_through_the_fetcher() {
+        let fixtures = FixtureFetch::new().with("https://a.test/me.png", [1, 2, 3]);
+        let loader = FetchLoader::new(Arc::new(fixtures));
+        let ctx = egui::Context::default();
+
+        assert!(matches!(loader.load(&ctx, "https://a.test/me.png"), Ok(BytesPoll::Pending { .. })));
+        match loader.load(&ctx, "https://a.test/me.png") {
+            Ok(BytesPoll::Ready { bytes, .. }) => assert_eq!(bytes.as_ref(), [1, 2, 3]),
+            _ => panic!("Fixture not loaded"),
+        }
+
+        loader.load(&ctx, "https://a.test/missing.png").ok();
+        assert!(matches!(loader.load(&ctx, "https://a.test/missing.png"), Err(LoadError::Loading(_))));
+        assert!(matches!(loader.load(&ctx, "bytes://logo.png"), Err(LoadError::NotSupported)));
+    }
+
+    #[test]
+    fn recordings_replay_what_was_fetched() {
+        let dir = std::env::temp_dir().join(format!("megui-recordings-{}", std::process::id()));
+        let upstream = Arc::new(FixtureFetch::new().with("https://a.test/cv?lang=en", "# Resume"));
+
+        let recorder = RecordedFetch::record(&dir, upstream);
+        assert_eq!(fetch_now(&recorder, "https://a.test/cv?lang=en").unwrap().text(), Some("# Resume"));
+
+        let replay = RecordedFetch::replay(&dir);
+        let replayed = fetch_now(&replay, "https://a.test/cv?lang=en").unwrap();
+        assert_eq!((replayed.status, replayed.text()), (200, Some("# Resume")));
+        assert!(fetch_now(&replay, "https://a.test/cv?lang=fr").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn local_folders_are_listed_as_artworks() {
+        let dir = std::env::temp_dir().join(format!("megui-gallery-{}", std::process::id()));
+        for name in ["beta", "alpha", ".git"] {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+        }
+        let local = LocalFetch::new(Arc::new(FixtureFetch::new().with("https://a.test/", "remote")));
+
+        let index = fetch_now(&local, &file_url(&dir.join("index.json"))).unwrap();
+        let index: Vec<crate::artwork::ArtworksResponse> = serde_json::from_slice(&index.bytes).unwrap();
+        let names: Vec<&str> = index[0].contents.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta"]);
+
+        std::fs::write(dir.join("index.json"), "[]").unwrap();
+        assert_eq!(fetch_now(&local, &file_url(&dir.join("index.json"))).unwrap().text(), Some("[]"));
+        assert_eq!(fetch_now(&local, &file_url(&dir.join("missing.png"))).unwrap().status, 404);
+        assert_eq!(fetch_now(&local, "https://a.test/").unwrap().text(), Some("remote"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
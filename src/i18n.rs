@@ -0,0 +1,392 @@
+use eframe::egui;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::artwork::Artwork;
+use crate::bidi;
+use crate::config::{Config, LocaleConfig, PageConfig, PageTranslation};
+
+/// Built-in message catalogs, by locale code
+const CATALOGS: [(&str, &str); 2] = [
+    ("en", include_str!("../locales/en.toml")),
+    ("fr", include_str!("../locales/fr.toml")),
+];
+
+/// Used for keys missing from the chosen catalog
+const FALLBACK_LOCALE: &str = "en";
+
+/// Locale setting that follows the browser (or `LANG` on native)
+pub const AUTO: &str = "auto";
+
+/// UI messages and translated config content for one locale
+pub struct I18n {
+    locale: String,
+    rtl: bool,
+    messages: HashMap<String, String>,
+    /// Messages in display order, for those with right-to-left text
+    visual: HashMap<String, String>,
+    /// Page titles in display order, by slug, for those with right-to-left text
+    visual_titles: HashMap<String, String>,
+    /// The blog's title in display order, if it has right-to-left text
+    visual_blog_title: Option<String>,
+    fallback: HashMap<String, String>,
+    content: LocaleConfig,
+    /// Locales to offer in settings, as code and name in that language
+    available: Vec<(String, String)>,
+}
+
+impl I18n {
+    pub const STORAGE_KEY: &'static str = "locale";
+
+    /// `setting` is a locale code or `AUTO`
+    pub fn new(setting: &str, config: &Config) -> Self {
+        let available = available(config);
+        let locale = if setting == AUTO {
+            system_locale()
+                .and_then(|tag| negotiate(&tag, &available))
+                .unwrap_or_else(|| FALLBACK_LOCALE.to_string())
+        } else {
+            negotiate(setting, &available).unwrap_or_else(|| FALLBACK_LOCALE.to_string())
+        };
+
+        let content = config.locales.get(&locale).cloned().unwrap_or_default();
+        let mut messages = catalog(&locale);
+        messages.extend(content.messages.clone());
+
+        let visual = messages
+            .iter()
+            .filter_map(|(key, message)| match bidi::visual(message) {
+                Cow::Owned(visual) => Some((key.clone(), visual)),
+                Cow::Borrowed(_) => None,
+            })
+            .collect();
+
+        let visual_titles = config
+            .pages
+            .iter()
+            .filter_map(|page| {
+                let title = content.pages.get(&page.slug).and_then(|t| t.title.as_deref()).unwrap_or(&page.title);
+                match bidi::visual(title) {
+                    Cow::Owned(visual) => Some((page.slug.clone(), visual)),
+                    Cow::Borrowed(_) => None,
+                }
+            })
+            .collect();
+
+        let blog_title = Self::configured_blog_title(&content, config);
+        let visual_blog_title = blog_title.map(bidi::visual).and_then(|title| match title {
+            Cow::Owned(visual) => Some(visual),
+            Cow::Borrowed(_) => None,
+        });
+
+        Self {
+            rtl: content.rtl.unwrap_or_else(|| bidi::is_rtl_locale(&locale)),
+            locale,
+            messages,
+            visual,
+            visual_titles,
+            visual_blog_title,
+            fallback: catalog(FALLBACK_LOCALE),
+            content,
+            available,
+        }
+    }
+
+    /// Code of the locale in use, e.g. "fr"
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Locales to offer in settings, as code and name in display order
+    pub fn available(&self) -> &[(String, String)] {
+        &self.available
+    }
+
+    /// Whether the UI is mirrored for a right-to-left language
+    pub fn is_rtl(&self) -> bool {
+        self.rtl
+    }
+
+    /// Top-down layout aligned to the reading side; rows added inside it run
+    /// right to left when the locale does
+    pub fn layout(&self) -> egui::Layout {
+        egui::Layout::top_down(if self.rtl { egui::Align::Max } else { egui::Align::Min })
+    }
+
+    /// The message for `key` in display order, in English if this locale lacks it
+    pub fn tr<'a>(&'a self, key: &'a str) -> &'a str {
+        self.visual.get(key).map_or_else(|| self.label(key), String::as_str)
+    }
+
+    /// The message for `key` with its `{name}` placeholders filled in, in display order
+    pub fn tr_args(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        // Placeholders are filled in before reordering, which would move the braces
+        bidi::visual(&self.label_args(key, args)).into_owned()
+    }
+
+    /// The message for `key` in reading order, for screen readers
+    pub fn label<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// `label` with its `{name}` placeholders filled in
+    pub fn label_args(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut message = self.label(key).to_string();
+        for (name, value) in args {
+            message = message.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        message
+    }
+
+    /// The page's title in display order
+    pub fn page_title<'a>(&'a self, page: &'a PageConfig) -> &'a str {
+        self.visual_titles
+            .get(&page.slug)
+            .map_or_else(|| self.page_label(page), String::as_str)
+    }
+
+    /// The page's title in reading order, for screen readers
+    pub fn page_label<'a>(&'a self, page: &'a PageConfig) -> &'a str {
+        self.page_translation(page)
+            .and_then(|t| t.title.as_deref())
+            .unwrap_or(&page.title)
+    }
+
+    /// The blog's title in display order
+    pub fn blog_title<'a>(&'a self, config: &'a Config) -> &'a str {
+        match &self.visual_blog_title {
+            Some(visual) => visual,
+            None => Self::configured_blog_title(&self.content, config).unwrap_or_else(|| self.tr("route-blog")),
+        }
+    }
+
+    /// The blog's title in reading order, for screen readers: the locale's
+    /// `route-blog` message from the config, else `[blog] title`, else the
+    /// catalog's
+    pub fn blog_label<'a>(&'a self, config: &'a Config) -> &'a str {
+        Self::configured_blog_title(&self.content, config).unwrap_or_else(|| self.label("route-blog"))
+    }
+
+    fn configured_blog_title<'a>(content: &'a LocaleConfig, config: &'a Config) -> Option<&'a str> {
+        content
+            .messages
+            .get("route-blog")
+            .or_else(|| config.blog.as_ref()?.title.as_ref())
+            .map(String::as_str)
+    }
+
+    /// A translation's own markdown or URL replaces both of the original's
+    pub fn page_source<'a>(&'a self, page: &'a PageConfig) -> (Option<&'a str>, Option<&'a str>) {
+        match self.page_translation(page) {
+            Some(t) if t.markdown.is_some() || t.url.is_some() => (t.markdown.as_deref(), t.url.as_deref()),
+            _ => (page.markdown.as_deref(), page.url.as_deref()),
+        }
+    }
+
+    pub fn artwork_info<'a>(&'a self, artwork: &'a Artwork) -> Option<&'a str> {
+        self.content
+            .artworks
+            .get(&artwork.name)
+            .or(artwork.info.as_ref())
+            .map(String::as_str)
+    }
+
+    fn page_translation(&self, page: &PageConfig) -> Option<&PageTranslation> {
+        self.content.pages.get(&page.slug)
+    }
+}
+
+/// Built-in and configured locales, as code and name in that language
+fn available(config: &Config) -> Vec<(String, String)> {
+    let mut codes: Vec<&str> = CATALOGS.iter().map(|(code, _)| *code).collect();
+    for code in config.locales.keys() {
+        if !codes.contains(&code.as_str()) {
+            codes.push(code);
+        }
+    }
+
+    codes
+        .into_iter()
+        .map(|code| {
+            let name = config
+                .locales
+                .get(code)
+                .and_then(|locale| locale.messages.get("language-name").cloned())
+                .or_else(|| catalog(code).remove("language-name"))
+                .map_or_else(|| code.to_string(), |name| bidi::visual(&name).into_owned());
+            (code.to_string(), name)
+        })
+        .collect()
+}
+
+/// Closest available locale to a tag like "fr-CA" or "pt_BR.UTF-8"
+fn negotiate(tag: &str, available: &[(String, String)]) -> Option<String> {
+    let tag = tag.split('.').next().unwrap_or(tag).replace('_', "-");
+    let language = tag.split('-').next().unwrap_or(&tag);
+    let code = [tag.as_str(), language].into_iter().find_map(|candidate| {
+        available
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(candidate))
+            .map(|(code, _)| code.clone())
+    });
+    code
+}
+
+fn catalog(locale: &str) -> HashMap<String, String> {
+    CATALOGS
+        .iter()
+        .find(|(code, _)| *code == locale)
+        .map(|(_, source)| toml::from_str(source).expect("Failed to parse message catalog"))
+        .unwrap_or_default()
+}
+
+/// The user's preferred language, e.g. "fr-FR"
+fn system_locale() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()?.navigator().language()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BlogConfig;
+
+    #[test]
+    fn catalogs_have_the_same_keys_and_placeholders() {
+        let english = catalog(FALLBACK_LOCALE);
+        let placeholders = |message: &str| {
+            let mut names: Vec<String> = message
+                .split('{')
+                .skip(1)
+                .filter_map(|rest| rest.split_once('}').map(|(name, _)| name.to_string()))
+                .collect();
+            names.sort();
+            names
+        };
+
+        for (code, _) in CATALOGS {
+            let messages = catalog(code);
+            for (key, message) in &english {
+                let translated = messages.get(key);
+                assert!(translated.is_some(), "{code} is missing {key}");
+                assert_eq!(placeholders(translated.unwrap()), placeholders(message), "{code}: {key}");
+            }
+            for key in messages.keys() {
+                assert!(english.contains_key(key), "{code} has unknown key {key}");
+            }
+        }
+    }
+
+    #[test]
+    fn locales_are_negotiated_and_messages_fall_back() {
+        let mut config = Config::default();
+        config.locales.insert(
+            "es".to_string(),
+            LocaleConfig {
+                messages: [("route-links".to_string(), "Enlaces".to_string())].into(),
+                ..Default::default()
+            },
+        );
+
+        let locales = available(&config);
+        assert_eq!(negotiate("fr-CA", &locales).as_deref(), Some("fr"));
+        assert_eq!(negotiate("fr_FR.UTF-8", &locales).as_deref(), Some("fr"));
+        assert_eq!(negotiate("ja", &locales), None);
+
+        let spanish = I18n::new("es-MX", &config);
+        assert_eq!(spanish.locale(), "es");
+        assert_eq!(spanish.tr("route-links"), "Enlaces");
+        assert_eq!(spanish.tr("route-resume"), "Resume");
+        assert_eq!(spanish.tr("no-such-key"), "no-such-key");
+
+        let french = I18n::new("fr", &config);
+        assert_eq!(french.tr_args("blog-page", &[("page", &2), ("count", &5)]), "Page 2 sur 5");
+        assert!(!french.is_rtl());
+    }
+
+    #[test]
+    fn blog_titles_are_translated() {
+        let mut config = Config {
+            blog: Some(BlogConfig {
+                index: "https://example.com/index.json".to_string(),
+                title: None,
+                position: 35,
+                per_page: 10,
+            }),
+            ..Default::default()
+        };
+        config.locales.insert(
+            "he".to_string(),
+            LocaleConfig {
+                messages: [("route-blog".to_string(), "רשימות".to_string())].into(),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(I18n::new("en", &config).blog_title(&config), "Blog");
+        let hebrew = I18n::new("he", &config);
+        assert_eq!(hebrew.blog_label(&config), "רשימות");
+        assert_eq!(hebrew.blog_title(&config), "תומישר");
+
+        // A configured title replaces the catalog's, but not a locale's own
+        config.blog.as_mut().unwrap().title = Some("Notes".to_string());
+        assert_eq!(I18n::new("fr", &config).blog_title(&config), "Notes");
+        assert_eq!(I18n::new("he", &config).blog_label(&config), "רשימות");
+    }
+
+    #[test]
+    fn mixed_case_locales_keep_their_configured_code() {
+        let mut config = Config::default();
+        config.locales.insert(
+            "pt-BR".to_string(),
+            LocaleConfig {
+                messages: [("route-links".to_string(), "Ligações".to_string())].into(),
+                ..Default::default()
+            },
+        );
+
+        let locales = available(&config);
+        assert_eq!(negotiate("pt_br.UTF-8", &locales).as_deref(), Some("pt-BR"));
+        let portuguese = I18n::new("pt-br", &config);
+        assert_eq!(portuguese.locale(), "pt-BR");
+        assert_eq!(portuguese.tr("route-links"), "Ligações");
+    }
+
+    #[test]
+    fn right_to_left_locales_show_messages_in_display_order() {
+        let mut config = Config::default();
+        config.locales.insert(
+            "he".to_string(),
+            LocaleConfig {
+                messages: [
+                    ("route-links".to_string(), "קישורים".to_string()),
+                    ("blog-page".to_string(), "עמוד {page} מתוך {count}".to_string()),
+                ]
+                .into(),
+                ..Default::default()
+            },
+        );
+
+        let hebrew = I18n::new("he-IL", &config);
+        assert!(hebrew.is_rtl());
+        assert_eq!(hebrew.tr("route-links"), "םירושיק");
+        assert_eq!(hebrew.tr_args("blog-page", &[("page", &2), ("count", &5)]), "5 ךותמ 2 דומע");
+        // Screen readers get the words in reading order
+        assert_eq!(hebrew.label("route-links"), "קישורים");
+        assert_eq!(hebrew.label_args("blog-page", &[("page", &2), ("count", &5)]), "עמוד 2 מתוך 5");
+        assert_eq!(hebrew.tr("route-resume"), "Resume");
+    }
+}
@@ -0,0 +1,189 @@
+//! `megui index`: an artworks index for a folder with one subfolder per
+//! artwork, in the format `artwork::parse_index` reads.
+
+use serde_json::{Map, Value};
+use std::path::Path;
+
+/// Written in the artworks folder
+pub const INDEX_FILE: &str = "index.json";
+
+/// Optional metadata in an artwork's folder; its keys are copied to the index
+pub const SIDECAR_FILE: &str = "artwork.toml";
+
+/// Generated in an artwork's folder from its first image
+pub const THUMBNAIL_FILE: &str = "thumbnail.png";
+
+/// Sidecar key naming the image to make the thumbnail from; not copied
+const IMAGE_KEY: &str = "image";
+
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "gif", "webp"];
+
+pub struct Index {
+    /// One object per artwork, by name
+    pub entries: Vec<Map<String, Value>>,
+    /// Artworks left without a thumbnail, and why
+    pub warnings: Vec<String>,
+}
+
+impl Index {
+    /// Pretty JSON with sorted keys, so regenerating an unchanged folder
+    /// gives the same file
+    pub fn to_json(&self) -> String {
+        let folder = serde_json::json!([{ "type": "directory", "name": ".", "contents": self.entries }]);
+        let mut json = serde_json::to_string_pretty(&folder).expect("JSON values serialize");
+        json.push('\n');
+        json
+    }
+}
+
+/// Index the subfolders of `dir`, writing thumbnails at most
+/// `thumbnail_size` pixels wide and high
+pub fn build(dir: &Path, thumbnail_size: u32) -> Result<Index, String> {
+    let mut index = Index {
+        entries: Vec::new(),
+        warnings: Vec::new(),
+    };
+    for name in subfolders(dir).map_err(|e| format!("{}: {}", dir.display(), e))? {
+        let entry = artwork_entry(&dir.join(&name), &name, thumbnail_size, &mut index.warnings)?;
+        index.entries.push(entry);
+    }
+    Ok(index)
+}
+
+/// Build the index of `dir` and write it to `dir/index.json`
+pub fn write(dir: &Path, thumbnail_size: u32) -> Result<Index, String> {
+    let index = build(dir, thumbnail_size)?;
+    let path = dir.join(INDEX_FILE);
+    std::fs::write(&path, index.to_json()).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(index)
+}
+
+fn artwork_entry(
+    folder: &Path,
+    name: &str,
+    thumbnail_size: u32,
+    warnings: &mut Vec<String>,
+) -> Result<Map<String, Value>, String> {
+    let mut entry = read_sidecar(folder)?;
+    if entry.contains_key("name") {
+        return Err(format!("{}: `name` is the folder's name and can't be set", folder.join(SIDECAR_FILE).display()));
+    }
+    let image = match entry.remove(IMAGE_KEY) {
+        Some(Value::String(image)) => Some(image),
+        Some(_) => return Err(format!("{}: `image` must be a file name", folder.join(SIDECAR_FILE).display())),
+        None => first_image(folder).map_err(|e| format!("{}: {}", folder.display(), e))?,
+    };
+
+    // A thumbnail given in the sidecar is used as it is
+    if !entry.contains_key("thumbnail") {
+        match image {
+            Some(image) => {
+                make_thumbnail(&folder.join(&image), &folder.join(THUMBNAIL_FILE), thumbnail_size)?;
+                entry.insert("thumbnail".to_string(), Value::String(THUMBNAIL_FILE.to_string()));
+            }
+            None => warnings.push(format!("{:?} has no image to make a thumbnail from", name)),
+        }
+    }
+
+    entry.insert("name".to_string(), Value::String(name.to_string()));
+    Ok(entry)
+}
+
+fn read_sidecar(folder: &Path) -> Result<Map<String, Value>, String> {
+    let path = folder.join(SIDECAR_FILE);
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Map::new()),
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+    let table: toml::Table = toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    match serde_json::to_value(table) {
+        Ok(Value::Object(map)) => Ok(map),
+        _ => Err(format!("{}: not representable as JSON", path.display())),
+    }
+}
+
+fn make_thumbnail(source: &Path, target: &Path, size: u32) -> Result<(), String> {
+    let image = image::open(source).map_err(|e| format!("{}: {}", source.display(), e))?;
+    image
+        .thumbnail(size, size)
+        .to_rgba8()
+        .save_with_format(target, image::ImageFormat::Png)
+        .map_err(|e| format!("{}: {}", target.display(), e))
+}
+
+/// Visible subfolders by name
+fn subfolders(dir: &Path) -> std::io::Result<Vec<String>> {
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if entry.path().is_dir() && !name.starts_with('.') {
+            names.push(name);
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// The image file that comes first by name, other than the thumbnail
+fn first_image(folder: &Path) -> std::io::Result<Option<String>> {
+    let mut images = Vec::new();
+    for entry in std::fs::read_dir(folder)? {
+        let Ok(name) = entry?.file_name().into_string() else {
+            continue;
+        };
+        let extension = Path::new(&name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        if name != THUMBNAIL_FILE && extension.is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.as_str())) {
+            images.push(name);
+        }
+    }
+    images.sort();
+    Ok(images.into_iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artwork;
+
+    #[test]
+    fn folders_are_indexed_with_metadata_and_thumbnails() {
+        let dir = std::env::temp_dir().join(format!("megui-index-{}", std::process::id()));
+        for name in ["sunrise", "dusk", ".drafts"] {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+        }
+        image::RgbaImage::new(800, 400).save(dir.join("sunrise/painting.png")).unwrap();
+        std::fs::write(
+            dir.join("sunrise/artwork.toml"),
+            "info = \"Oil on canvas\"\nyear = 2024\ntags = [\"landscape\"]\n",
+        )
+        .unwrap();
+
+        let index = write(&dir, 200).unwrap();
+        assert_eq!(index.warnings, ["\"dusk\" has no image to make a thumbnail from"]);
+        let thumbnail = image::open(dir.join("sunrise/thumbnail.png")).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (200, 100));
+
+        let json = std::fs::read_to_string(dir.join(INDEX_FILE)).unwrap();
+        let artworks = artwork::parse_index(&json).unwrap();
+        let names: Vec<&str> = artworks.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["dusk", "sunrise"]);
+        assert_eq!(artworks[1].info.as_deref(), Some("Oil on canvas"));
+        assert_eq!(artworks[1].thumbnail.as_deref(), Some(THUMBNAIL_FILE));
+        assert!(json.contains("\"year\": 2024"));
+
+        // Same folder, same file
+        assert_eq!(build(&dir, 200).unwrap().to_json(), json);
+
+        std::fs::write(dir.join("dusk/artwork.toml"), "name = \"dawn\"").unwrap();
+        assert!(build(&dir, 200).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,280 @@
+use ammonia::Url;
+use pulldown_cmark::{Event, LinkType, Parser, Tag};
+use std::ops::Range;
+
+use crate::config::Config;
+use crate::markdown;
+use crate::routes::Route;
+
+/// Where a link in the resume should take the user
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkTarget {
+    /// A heading slug on the resume page
+    Section(String),
+    /// A page of this app, with the resume section or blog post to show if any
+    Route(Route, Option<String>),
+    /// Anything else, opened in the browser
+    External(String),
+}
+
+/// Resolves resume URLs against where the resume was fetched from
+pub struct LinkResolver {
+    resume: Option<Url>,
+    /// Pages that serve this app: the website and, on web, the current page
+    sites: Vec<Url>,
+}
+
+impl LinkResolver {
+    pub fn new(resume_url: &str, website_url: &str) -> Self {
+        Self {
+            resume: Url::parse(resume_url).ok(),
+            sites: Url::parse(website_url).ok().into_iter().chain(Self::app_url()).collect(),
+        }
+    }
+
+    /// The page the web app is running on
+    fn app_url() -> Option<Url> {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let href = web_sys::window()?.location().href().ok()?;
+            Url::parse(&href).ok()
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            None
+        }
+    }
+
+    /// Absolute form of a URL found in the resume.
+    /// Fragments (`#work`) and app routes (`#/artworks`) are left as they are.
+    pub fn resolve(&self, url: &str) -> String {
+        if url.starts_with('#') || Url::parse(url).is_ok() {
+            return url.to_string();
+        }
+        self.resume
+            .as_ref()
+            .and_then(|base| base.join(url).ok())
+            .map_or_else(|| url.to_string(), String::from)
+    }
+
+    /// Make relative image URLs in markdown absolute so image loaders can fetch
+    /// them, and relative link URLs too when `links` is set
+    pub fn resolve_markdown(&self, markdown: &str, links: bool) -> String {
+        let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+
+        for (event, range) in Parser::new(markdown).into_offset_iter() {
+            let (link_type, dest_url) = match &event {
+                Event::Start(Tag::Image { link_type, dest_url, .. }) => (link_type, dest_url),
+                Event::Start(Tag::Link { link_type, dest_url, .. }) if links => (link_type, dest_url),
+                _ => continue,
+            };
+            // Reference definitions and autolinks are left alone
+            if *link_type != LinkType::Inline {
+                continue;
+            }
+            let resolved = self.resolve(dest_url);
+            if resolved == dest_url.as_ref() {
+                continue;
+            }
+
+            // The destination follows the last `](`, possibly in angle brackets.
+            // Destinations written with escapes don't match and are skipped.
+            let source = &markdown[range.clone()];
+            let Some(open) = source.rfind("](") else {
+                continue;
+            };
+            let rest = &source[open + 2..];
+            let trimmed = rest.trim_start();
+            let mut start = range.start + open + 2 + (rest.len() - trimmed.len());
+            let trimmed = match trimmed.strip_prefix('<') {
+                Some(inner) => {
+                    start += 1;
+                    inner
+                }
+                None => trimmed,
+            };
+            if trimmed.starts_with(dest_url.as_ref()) {
+                edits.push((start..start + dest_url.len(), resolved));
+            }
+        }
+
+        // A linked image's own URL comes before the link's
+        edits.sort_by_key(|(range, _)| range.start);
+
+        let mut resolved = String::with_capacity(markdown.len());
+        let mut last = 0;
+        for (range, url) in edits {
+            resolved.push_str(&markdown[last..range.start]);
+            resolved.push_str(&url);
+            last = range.end;
+        }
+        resolved.push_str(&markdown[last..]);
+        resolved
+    }
+
+    /// Sort a clicked link into an in-page jump, an app page or an external URL
+    pub fn target(&self, url: &str, config: &Config) -> LinkTarget {
+        if url.starts_with("#/") {
+            return Self::route_target(url, config).unwrap_or_else(|| LinkTarget::External(url.to_string()));
+        }
+        if let Some(fragment) = url.strip_prefix('#') {
+            return Self::section_target(fragment);
+        }
+
+        let Ok(absolute) = Url::parse(&self.resolve(url)) else {
+            return LinkTarget::External(url.to_string());
+        };
+
+        if self.resume.as_ref().is_some_and(|resume| same_document(&absolute, resume)) {
+            return match absolute.fragment() {
+                Some(fragment) if fragment.starts_with('/') => Self::route_target(&format!("#{}", fragment), config)
+                    .unwrap_or(LinkTarget::Route(Route::Resume, None)),
+                Some(fragment) => Self::section_target(fragment),
+                None => LinkTarget::Route(Route::Resume, None),
+            };
+        }
+
+        // `https://<site>/#/artworks` style links open the page in the app
+        if let Some(fragment) = absolute.fragment().filter(|f| f.starts_with('/')) {
+            if self.sites.iter().any(|site| same_document(&absolute, site)) {
+                if let Some(target) = Self::route_target(&format!("#{}", fragment), config) {
+                    return target;
+                }
+            }
+        }
+
+        LinkTarget::External(absolute.to_string())
+    }
+
+    fn section_target(fragment: &str) -> LinkTarget {
+        let slug = markdown::slugify(fragment);
+        if slug.is_empty() {
+            LinkTarget::Route(Route::Resume, None)
+        } else {
+            LinkTarget::Section(slug)
+        }
+    }
+
+    /// `#/resume/<section>` and `#/blog/<post>` style hashes
+    fn route_target(hash: &str, config: &Config) -> Option<LinkTarget> {
+        let route = Route::from_hash(hash, config)?;
+        let (_, section) = Route::split_section(hash);
+        Some(LinkTarget::Route(route, section.map(str::to_string)))
+    }
+}
+
+/// Same page, ignoring the fragment, a trailing slash and `index.html`
+fn same_document(a: &Url, b: &Url) -> bool {
+    let path = |url: &Url| url.path().trim_end_matches("index.html").trim_end_matches('/').to_string();
+    a.origin() == b.origin() && path(a) == path(b) && a.query() == b.query()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESUME: &str = "https://example.com/cv/resume.md";
+
+    fn resolver() -> LinkResolver {
+        LinkResolver::new(RESUME, "https://me.example.org/")
+    }
+
+    #[test]
+    fn relative_images_are_resolved_and_links_only_when_asked() {
+        let links = resolver();
+        let markdown = "![me](me.png) [notes](../notes.html) [abs](https://other.org/x) [top](#work)";
+        assert_eq!(
+            links.resolve_markdown(markdown, false),
+            "![me](https://example.com/cv/me.png) [notes](../notes.html) [abs](https://other.org/x) [top](#work)"
+        );
+        assert_eq!(
+            links.resolve_markdown(markdown, true),
+            "![me](https://example.com/cv/me.png) [notes](https://example.com/notes.html) \
+             [abs](https://other.org/x) [top](#work)"
+        );
+    }
+
+    #[test]
+    fn nested_brackets_and_parentheses_keep_their_markup() {
+        let links = resolver();
+        assert_eq!(
+            links.resolve_markdown("[a [nested] label](page.html)", true),
+            "[a [nested] label](https://example.com/cv/page.html)"
+        );
+        assert_eq!(
+            links.resolve_markdown("[![badge](badge.svg)](project/)", true),
+            "[![badge](https://example.com/cv/badge.svg)](https://example.com/cv/project/)"
+        );
+        assert_eq!(
+            links.resolve_markdown("![photo](<photo (1).png>)", false),
+            "![photo](<https://example.com/cv/photo%20(1).png>)"
+        );
+        assert_eq!(links.resolve_markdown("[f(x)](f(x).html)", true), "[f(x)](https://example.com/cv/f(x).html)");
+        // Reference links and autolinks are left as written
+        let reference = "[ref][1]\n\n[1]: other.html\n\n<https://a.b/c>";
+        assert_eq!(links.resolve_markdown(reference, true), reference);
+    }
+
+    #[test]
+    fn fragments_and_same_document_links_jump_to_sections() {
+        let links = resolver();
+        let config = Config::default();
+        assert_eq!(links.target("#Work History", &config), LinkTarget::Section("work-history".to_string()));
+        assert_eq!(links.target("#", &config), LinkTarget::Route(Route::Resume, None));
+        assert_eq!(links.target("resume.md#skills", &config), LinkTarget::Section("skills".to_string()));
+        assert_eq!(
+            links.target("https://example.com/cv/resume.md#skills", &config),
+            LinkTarget::Section("skills".to_string())
+        );
+        assert_eq!(links.target(RESUME, &config), LinkTarget::Route(Route::Resume, None));
+    }
+
+    #[test]
+    fn app_routes_open_in_the_app() {
+        let links = resolver();
+        let config = Config::default();
+        assert_eq!(links.target("#/artworks", &config), LinkTarget::Route(Route::Artworks, None));
+        assert_eq!(
+            links.target("#/resume/education", &config),
+            LinkTarget::Route(Route::Resume, Some("education".to_string()))
+        );
+        assert_eq!(
+            links.target("https://me.example.org/#/resume/work", &config),
+            LinkTarget::Route(Route::Resume, Some("work".to_string()))
+        );
+        assert_eq!(
+            links.target("https://me.example.org/index.html#/artworks", &config),
+            LinkTarget::Route(Route::Artworks, None)
+        );
+        // Unknown routes are left to the browser
+        assert_eq!(links.target("#/nowhere", &config), LinkTarget::External("#/nowhere".to_string()));
+    }
+
+    #[test]
+    fn other_urls_are_external() {
+        let links = resolver();
+        let config = Config::default();
+        assert_eq!(
+            links.target("https://other.org/#/artworks", &config),
+            LinkTarget::External("https://other.org/#/artworks".to_string())
+        );
+        assert_eq!(
+            links.target("projects.html", &config),
+            LinkTarget::External("https://example.com/cv/projects.html".to_string())
+        );
+        assert_eq!(
+            links.target("mailto:me@example.org", &config),
+            LinkTarget::External("mailto:me@example.org".to_string())
+        );
+    }
+
+    #[test]
+    fn same_document_ignores_fragments_slashes_and_index() {
+        let url = |s: &str| Url::parse(s).unwrap();
+        assert!(same_document(&url("https://a.b/x/#top"), &url("https://a.b/x")));
+        assert!(same_document(&url("https://a.b/x/index.html"), &url("https://a.b/x/")));
+        assert!(!same_document(&url("https://a.b/x?page=2"), &url("https://a.b/x")));
+        assert!(!same_document(&url("http://a.b/x"), &url("https://a.b/x")));
+        assert!(!same_document(&url("https://a.b/y"), &url("https://a.b/x")));
+    }
+}
@@ -1,7 +1,24 @@
 mod app;
 mod artwork;
+mod bidi;
+mod blog;
+#[cfg(not(target_arch = "wasm32"))]
+mod check;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
 mod config;
+mod fetch;
+mod i18n;
+#[cfg(not(target_arch = "wasm32"))]
+mod index;
+mod links;
+mod markdown;
+mod pdf;
+mod resume;
 mod routes;
+mod sanitize;
+#[cfg(not(target_arch = "wasm32"))]
+mod window;
 mod components;
 mod pages;
 
@@ -10,21 +27,38 @@ use app::MeguiApp;
 fn main() -> Result<(), eframe::Error> {
     #[cfg(not(target_arch = "wasm32"))]
     {
-        let options = eframe::NativeOptions {
-            viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 600.0]),
-            ..Default::default()
-        };
+        use clap::{CommandFactory, Parser};
 
-        eframe::run_native(
-            "megui",
+        let cli = cli::Cli::parse();
+        if let Some(command) = &cli.command {
+            std::process::exit(cli.run(command));
+        }
+
+        let start = cli
+            .start_options()
+            .unwrap_or_else(|e| cli::Cli::command().error(clap::error::ErrorKind::InvalidValue, e).exit());
+        let fetcher = cli.fetcher();
+
+        let monitors = window::Monitors::default();
+        let options = cli.native_options(&start.config, monitors.clone());
+
+        // Our own event loop, so monitors can be listed before the window opens
+        let event_loop = winit::event_loop::EventLoop::<eframe::UserEvent>::with_user_event().build()?;
+        let app = eframe::create_native(
+            cli::APP_ID,
             options,
-            Box::new(|_cc| Ok(Box::new(MeguiApp::default()))),
-        )
+            Box::new(move |cc| Ok(Box::new(MeguiApp::new(cc, start, fetcher)))),
+            &event_loop,
+        );
+        event_loop.run_app(&mut window::WithMonitors::new(app, monitors))?;
+        Ok(())
     }
 
     #[cfg(target_arch = "wasm32")]
     {
+        use app::StartOptions;
         use eframe::wasm_bindgen::JsCast;
+        use std::sync::Arc;
 
         // Redirect tracing to console.log and friends:
         eframe::WebLogger::init(log::LevelFilter::Debug).ok();
@@ -47,7 +81,7 @@ fn main() -> Result<(), eframe::Error> {
                 .start(
                     canvas,
                     web_options,
-                    Box::new(|_cc| Ok(Box::new(MeguiApp::default()))),
+                    Box::new(|cc| Ok(Box::new(MeguiApp::new(cc, StartOptions::default(), Arc::new(fetch::HttpFetch))))),
                 )
                 .await
                 .expect("failed to start eframe");
@@ -56,3 +90,4 @@ fn main() -> Result<(), eframe::Error> {
         Ok(())
     }
 }
+
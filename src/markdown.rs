@@ -0,0 +1,258 @@
+use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// Headings up to this level start a new section in the outline
+pub const OUTLINE_MAX_LEVEL: u8 = 3;
+
+/// A heading found in a markdown document
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heading {
+    pub level: u8,
+    pub text: String,
+    /// Unique within the document, used for `#/resume/<slug>` links
+    pub slug: String,
+}
+
+/// A heading and the markdown up to the next outline heading
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// `None` for any content before the first heading
+    pub heading: Option<Heading>,
+    pub markdown: String,
+}
+
+/// Collect headings in document order.
+/// Handles both ATX (`## Title`) and setext (`Title\n===`) styles, which
+/// html2md mixes in its output.
+pub fn headings(markdown: &str) -> Vec<Heading> {
+    headings_with_offsets(markdown)
+        .into_iter()
+        .map(|(heading, _)| heading)
+        .collect()
+}
+
+/// Split the document at every heading up to `OUTLINE_MAX_LEVEL`
+pub fn sections(markdown: &str) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+    let mut heading = None;
+
+    for (next_heading, offset) in headings_with_offsets(markdown) {
+        if next_heading.level > OUTLINE_MAX_LEVEL {
+            continue;
+        }
+        let content = &markdown[start..offset];
+        if heading.is_some() || !content.trim().is_empty() {
+            sections.push(Section {
+                heading: heading.take(),
+                markdown: content.to_string(),
+            });
+        }
+        start = offset;
+        heading = Some(next_heading);
+    }
+
+    sections.push(Section {
+        heading,
+        markdown: markdown[start..].to_string(),
+    });
+    sections
+}
+
+/// URL-friendly version of a heading: lowercase words joined by dashes
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Plain text of each run that egui_commonmark lays out as a single label:
+/// text between formatting changes, whole links and whole code blocks
+pub fn text_runs(markdown: &str) -> Vec<String> {
+    let mut runs = Vec::new();
+    // Open link, image or code block collecting its text into one run
+    let mut grouped: Option<String> = None;
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Link { .. } | Tag::Image { .. } | Tag::CodeBlock(_)) => {
+                grouped = Some(String::new());
+            }
+            Event::End(TagEnd::Link | TagEnd::Image | TagEnd::CodeBlock) => {
+                runs.extend(grouped.take());
+            }
+            Event::Text(text) | Event::Code(text) | Event::InlineHtml(text) | Event::Html(text) => {
+                match &mut grouped {
+                    Some(run) => run.push_str(&text),
+                    None => runs.push(text.to_string()),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    runs
+}
+
+/// Case-insensitive, non-overlapping occurrences of `query` as char ranges
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let query: Vec<char> = query.chars().map(fold).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let text: Vec<char> = text.chars().map(fold).collect();
+    let mut matches = Vec::new();
+    let mut idx = 0;
+    while idx + query.len() <= text.len() {
+        if text[idx..idx + query.len()] == query[..] {
+            matches.push(idx..idx + query.len());
+            idx += query.len();
+        } else {
+            idx += 1;
+        }
+    }
+    matches
+}
+
+/// Headings with the byte offset where each one starts
+fn headings_with_offsets(markdown: &str) -> Vec<(Heading, usize)> {
+    let mut headings = Vec::new();
+    let mut slug_counts: HashMap<String, usize> = HashMap::new();
+    let mut used: HashSet<String> = HashSet::new();
+    let mut current: Option<(u8, String, usize)> = None;
+
+    for (event, range) in Parser::new(markdown).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some((heading_level(level), String::new(), range.start));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, heading_text, _)) = &mut current {
+                    heading_text.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, text, offset)) = current.take() {
+                    let text = text.trim().to_string();
+                    if text.is_empty() {
+                        continue;
+                    }
+
+                    // Repeated headings get -2, -3, ... suffixes
+                    let mut base = slugify(&text);
+                    if base.is_empty() {
+                        base = "section".to_string();
+                    }
+                    // skipping any taken by a heading that ends in a number
+                    let count = slug_counts.entry(base.clone()).or_insert(0);
+                    let mut slug = base.clone();
+                    while used.contains(&slug) {
+                        *count += 1;
+                        slug = format!("{}-{}", base, *count + 1);
+                    }
+                    used.insert(slug.clone());
+
+                    headings.push((Heading { level, text, slug }, offset));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    headings
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_fold_case_and_do_not_overlap() {
+        assert_eq!(find_matches("Rust and RUST and rust", "rust"), [0..4, 9..13, 18..22]);
+        assert_eq!(find_matches("aaaa", "aa"), [0..2, 2..4]);
+        assert_eq!(find_matches("aaa", "aa"), vec![0..2]);
+        // Ranges count chars, not bytes
+        assert_eq!(find_matches("Élan, ÉLAN", "élan"), [0..4, 6..10]);
+        assert!(find_matches("anything", "").is_empty());
+        assert!(find_matches("short", "longer query").is_empty());
+    }
+
+    #[test]
+    fn runs_split_at_formatting_but_keep_links_and_code_blocks_whole() {
+        let markdown = "Built **fast** apps with [the *egui* crate](https://egui.rs) and `cargo`.\n\n```\nfn main() {}\n```\n";
+        assert_eq!(
+            text_runs(markdown),
+            ["Built ", "fast", " apps with ", "the egui crate", " and ", "cargo", ".", "fn main() {}\n"]
+        );
+    }
+
+    #[test]
+    fn a_query_split_by_markup_is_not_a_match() {
+        let runs = text_runs("Rust**acean**");
+        assert_eq!(runs, ["Rust", "acean"]);
+        assert!(runs.iter().all(|run| find_matches(run, "rustacean").is_empty()));
+    }
+
+    #[test]
+    fn slugs() {
+        let cases = [
+            ("Work History", "work-history"),
+            ("  C++ & Rust!  ", "c-rust"),
+            ("Éducation", "éducation"),
+            ("日本語の経歴", "日本語の経歴"),
+            ("2019 – 2021", "2019-2021"),
+            ("---", ""),
+        ];
+        for (text, slug) in cases {
+            assert_eq!(slugify(text), slug, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn repeated_headings_get_unique_slugs() {
+        let markdown = "# Work\n\n## Work\n\n## Work 2\n\n## Work\n\n## ???\n\n## !!!\n";
+        let slugs: Vec<String> = headings(markdown).into_iter().map(|h| h.slug).collect();
+        assert_eq!(slugs, ["work", "work-2", "work-2-2", "work-3", "section", "section-2"]);
+    }
+
+    #[test]
+    fn sections_split_at_outline_headings() {
+        let markdown = "Intro\n\n# Ada\n\nText\n\nSetext\n------\n\n#### Detail\n\nMore\n";
+        let split = sections(markdown);
+        let outline: Vec<(Option<&str>, &str)> = split
+            .iter()
+            .map(|s| (s.heading.as_ref().map(|h| h.slug.as_str()), s.markdown.as_str()))
+            .collect();
+        assert_eq!(
+            outline,
+            [
+                (None, "Intro\n\n"),
+                (Some("ada"), "# Ada\n\nText\n\n"),
+                (Some("setext"), "Setext\n------\n\n#### Detail\n\nMore\n"),
+            ]
+        );
+        // Nothing before the first heading, so no leading section
+        assert_eq!(sections("# Only\n").len(), 1);
+        assert_eq!(sections(""), [Section { heading: None, markdown: String::new() }]);
+    }
+}
@@ -1,7 +1,10 @@
-use eframe::egui;
+use eframe::egui::{self, accesskit::Role};
 
 use crate::artwork::Artwork;
+use crate::bidi;
+use crate::components::a11y;
 use crate::config::Config;
+use crate::i18n::I18n;
 
 #[cfg(target_arch = "wasm32")]
 use hframe::HtmlWindow;
@@ -9,23 +12,28 @@ use hframe::HtmlWindow;
 pub struct ArtworksPage;
 
 impl ArtworksPage {
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         ui: &mut egui::Ui,
         ctx: &egui::Context,
         config: &Config,
+        i18n: &I18n,
         artworks: &[Artwork],
         selected_artworks: &mut Vec<Artwork>,
+        search_query: &mut String,
         loading: bool,
         error: &Option<String>,
     ) {
         // Heading
-        ui.heading("Artworks");
+        ui.heading(i18n.tr("route-artworks"));
         ui.add_space(5.0);
         ui.separator();
         ui.add_space(10.0);
 
         // Open artworks website button
-        if ui.button("🔗 Open Artworks Website").clicked() {
+        let website_button = ui.button(i18n.tr("artworks-open-website"));
+        a11y::set_label(&website_button, i18n.label("artworks-open-website-label"));
+        if website_button.clicked() {
             let artworks_base = config.app.artworks.trim_end_matches("/index.json");
             ctx.open_url(egui::OpenUrl::new_tab(artworks_base));
         }
@@ -36,38 +44,119 @@ impl ArtworksPage {
 
         // Error display
         if let Some(error) = error {
-            ui.colored_label(egui::Color32::RED, format!("Error: {}", error));
+            ui.colored_label(ui.visuals().error_fg_color, i18n.tr_args("error", &[("error", error)]));
             ui.add_space(10.0);
         }
 
+        // Search field
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            let search = ui.add(
+                egui::TextEdit::singleline(search_query)
+                    .id(Self::search_id())
+                    .hint_text(i18n.tr("artworks-search")),
+            );
+            a11y::set_label(&search, i18n.label("artworks-search"));
+        });
+        ui.add_space(10.0);
+
         // Artworks list
-        Self::render_artworks_list(ui, ctx, config, artworks, selected_artworks, loading);
+        let query = search_query.trim().to_lowercase();
+        let filtered: Vec<&Artwork> = artworks
+            .iter()
+            .filter(|artwork| query.is_empty() || Self::matches(i18n, artwork, &query))
+            .collect();
+        if !artworks.is_empty() && filtered.is_empty() {
+            ui.label(i18n.tr("artworks-no-results"));
+        }
+        Self::render_artworks_list(ui, ctx, config, i18n, &filtered, selected_artworks, loading);
+    }
+
+    /// Id of the search field, so shortcuts can focus it
+    pub fn search_id() -> egui::Id {
+        egui::Id::new("artworks_search")
+    }
+
+    fn matches(i18n: &I18n, artwork: &Artwork, query: &str) -> bool {
+        artwork.name.to_lowercase().contains(query)
+            || i18n
+                .artwork_info(artwork)
+                .is_some_and(|info| info.to_lowercase().contains(query))
+    }
+
+    fn window_id_source(name: &str) -> String {
+        format!("artwork_window_{}", name)
+    }
+
+    /// Where the choice to show an artwork in its own native window is kept
+    fn popped_out_id(name: &str) -> egui::Id {
+        egui::Id::new(Self::window_id_source(name)).with("popped_out")
+    }
+
+    /// Whether the artwork is shown in its own native window rather than
+    /// inside the main one
+    pub fn is_popped_out(ctx: &egui::Context, name: &str) -> bool {
+        ctx.data(|d| d.get_temp(Self::popped_out_id(name)).unwrap_or(false))
+    }
+
+    /// Close the artwork window that is on top, or the most recently opened
+    /// one still inside the main window
+    pub fn close_topmost_window(ctx: &egui::Context, selected_artworks: &mut Vec<Artwork>) {
+        let top_layer = ctx.memory(|mem| mem.areas().top_layer_id(egui::Order::Middle));
+        let idx = top_layer
+            .and_then(|layer| {
+                selected_artworks
+                    .iter()
+                    .position(|a| egui::Id::new(Self::window_id_source(&a.name)) == layer.id)
+            })
+            .or_else(|| selected_artworks.iter().rposition(|a| !Self::is_popped_out(ctx, &a.name)));
+
+        if let Some(idx) = idx {
+            Self::close_window(ctx, selected_artworks, idx);
+        }
+    }
+
+    /// Drop an artwork window, forgetting whether it was popped out so it
+    /// opens inside the main window next time
+    fn close_window(ctx: &egui::Context, selected_artworks: &mut Vec<Artwork>, idx: usize) {
+        let artwork = selected_artworks.remove(idx);
+        ctx.data_mut(|d| d.remove::<bool>(Self::popped_out_id(&artwork.name)));
     }
 
     fn render_artworks_list(
         ui: &mut egui::Ui,
         ctx: &egui::Context,
         config: &Config,
-        artworks: &[Artwork],
+        i18n: &I18n,
+        artworks: &[&Artwork],
         selected_artworks: &mut Vec<Artwork>,
         loading: bool,
     ) {
         if !artworks.is_empty() {
             egui::ScrollArea::vertical().show(ui, |ui| {
+                a11y::set_role(ui, Role::List, Some(i18n.label("route-artworks")));
                 for artwork in artworks {
                     ui.horizontal(|ui| {
-                        if ui.button(&artwork.name).clicked() {
+                        a11y::set_role(ui, Role::ListItem, Some(&artwork.name));
+                        if ui.button(bidi::visual(&artwork.name)).clicked() {
                             // Add to selected artworks if not already open
                             if !selected_artworks.iter().any(|a| a.name == artwork.name) {
-                                selected_artworks.push(artwork.clone());
+                                selected_artworks.push((*artwork).clone());
                             }
                         }
-                        if let Some(info) = &artwork.info {
-                            ui.label(info);
+                        if let Some(info) = i18n.artwork_info(artwork) {
+                            bidi::label(ui, info);
                         }
 
                         // Add "open in new tab" button
-                        if ui.small_button("🔗").on_hover_text("Open in New Tab").clicked() {
+                        let new_tab_button = ui
+                            .small_button("🔗")
+                            .on_hover_text(i18n.tr("open-in-new-tab-label"));
+                        a11y::set_label(
+                            &new_tab_button,
+                            &i18n.label_args("artworks-open-artwork-label", &[("name", &artwork.name)]),
+                        );
+                        if new_tab_button.clicked() {
                             let artworks_base = config.app.artworks.trim_end_matches("/index.json");
                             let artwork_url = format!("{}/{}/", artworks_base, artwork.name);
                             ctx.open_url(egui::OpenUrl::new_tab(&artwork_url));
@@ -77,13 +166,14 @@ impl ArtworksPage {
             });
         } else if loading {
             ui.spinner();
-            ui.label("Loading artworks...");
+            ui.label(i18n.tr("artworks-loading"));
         }
     }
 
     pub fn render_artwork_modals(
         ctx: &egui::Context,
         config: &Config,
+        i18n: &I18n,
         selected_artworks: &mut Vec<Artwork>,
     ) {
         let mut to_remove = Vec::new();
@@ -99,7 +189,7 @@ impl ArtworksPage {
             );
 
             HtmlWindow::new(&artwork.name)
-                .id(&format!("artwork_window_{}", artwork.name))
+                .id(&Self::window_id_source(&artwork.name))
                 .open(&mut open)
                 .content(&iframe_content)
                 .show(ctx);
@@ -112,32 +202,48 @@ impl ArtworksPage {
         #[cfg(not(target_arch = "wasm32"))]
         for (idx, artwork) in selected_artworks.iter().enumerate() {
             let mut open = true;
+            let mut toggle_pop_out = false;
             let artworks_base = config.app.artworks.trim_end_matches("/index.json");
             let artwork_url = format!("{}/{}/", artworks_base, artwork.name);
+            let id = egui::Id::new(Self::window_id_source(&artwork.name));
+            let popped_out = Self::is_popped_out(ctx, &artwork.name);
 
-            egui::Window::new(&artwork.name)
-                .id(egui::Id::new(format!("artwork_window_{}", artwork.name)))
-                .open(&mut open)
-                .resizable(true)
-                .default_width(800.0)
-                .default_height(600.0)
-                .show(ctx, |ui| {
-                    if let Some(info) = &artwork.info {
-                        ui.label(info);
-                        ui.add_space(5.0);
-                    }
+            let window = || {
+                egui::Window::new(bidi::visual(&artwork.name))
+                    .id(id)
+                    .resizable(true)
+                    .default_width(800.0)
+                    .default_height(600.0)
+            };
 
-                    if ui.button("🔗 Open in New Tab").clicked() {
-                        ctx.open_url(egui::OpenUrl::new_tab(&artwork_url));
+            if popped_out {
+                let viewport = egui::ViewportBuilder::default()
+                    .with_title(&artwork.name)
+                    .with_inner_size([800.0, 600.0]);
+                ctx.show_viewport_immediate(egui::ViewportId(id), viewport, |ctx, class| {
+                    if class == egui::ViewportClass::Embedded {
+                        // The backend can't open more windows; show it inside after all
+                        window().open(&mut open).show(ctx, |ui| {
+                            toggle_pop_out = Self::render_artwork_details(ui, ctx, config, i18n, artwork, &artwork_url, true);
+                        });
+                        return;
                     }
-
-                    ui.add_space(5.0);
-                    ui.separator();
-                    ui.add_space(5.0);
-                    ui.label("Note: iframe preview only available in web version.");
-                    ui.label("Click 'Open in New Tab' to view the artwork.");
+                    egui::CentralPanel::default().show(ctx, |ui| {
+                        toggle_pop_out = Self::render_artwork_details(ui, ctx, config, i18n, artwork, &artwork_url, true);
+                    });
+                    if ctx.input(|i| i.viewport().close_requested() || i.key_pressed(egui::Key::Escape)) {
+                        open = false;
+                    }
+                });
+            } else {
+                window().open(&mut open).show(ctx, |ui| {
+                    toggle_pop_out = Self::render_artwork_details(ui, ctx, config, i18n, artwork, &artwork_url, false);
                 });
+            }
 
+            if toggle_pop_out {
+                ctx.data_mut(|d| d.insert_temp(Self::popped_out_id(&artwork.name), !popped_out));
+            }
             if !open {
                 to_remove.push(idx);
             }
@@ -145,7 +251,58 @@ impl ArtworksPage {
 
         // Remove closed artworks in reverse order to maintain correct indices
         for idx in to_remove.iter().rev() {
-            selected_artworks.remove(*idx);
+            Self::close_window(ctx, selected_artworks, *idx);
+        }
+    }
+
+    /// Contents of a native artwork window; returns whether the button
+    /// moving it in or out of the main window was clicked
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_artwork_details(
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        config: &Config,
+        i18n: &I18n,
+        artwork: &Artwork,
+        artwork_url: &str,
+        popped_out: bool,
+    ) -> bool {
+        if let Some(info) = i18n.artwork_info(artwork) {
+            bidi::label(ui, info);
+            ui.add_space(5.0);
+        }
+
+        let mut toggle_pop_out = false;
+        ui.horizontal(|ui| {
+            let new_tab_button = ui.button(i18n.tr("open-in-new-tab"));
+            a11y::set_label(&new_tab_button, i18n.label("open-in-new-tab-label"));
+            if new_tab_button.clicked() {
+                ctx.open_url(egui::OpenUrl::new_tab(artwork_url));
+            }
+
+            let (text, label) = match popped_out {
+                true => ("artworks-pop-in", "artworks-pop-in-label"),
+                false => ("artworks-pop-out", "artworks-pop-out-label"),
+            };
+            let pop_button = ui.button(i18n.tr(text)).on_hover_text(i18n.tr(label));
+            a11y::set_label(&pop_button, i18n.label(label));
+            toggle_pop_out = pop_button.clicked();
+        });
+
+        ui.add_space(5.0);
+        ui.separator();
+        ui.add_space(5.0);
+        // The artwork itself is a web page, which only the web build can embed
+        if let Some(thumbnail) = artwork.thumbnail_url(&config.app.artworks) {
+            let image = egui::Image::new(thumbnail)
+                .max_width(ui.available_width())
+                .max_height(ui.available_height() - 2.0 * ui.text_style_height(&egui::TextStyle::Body))
+                .alt_text(i18n.label_args("artworks-thumbnail-label", &[("name", &artwork.name)]));
+            ui.add(image);
+            ui.add_space(5.0);
         }
+        ui.label(i18n.tr("artworks-native-note"));
+        ui.label(i18n.tr("artworks-native-hint"));
+        toggle_pop_out
     }
 }
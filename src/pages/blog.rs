@@ -0,0 +1,217 @@
+use eframe::egui;
+use egui_commonmark::CommonMarkCache;
+use std::collections::HashMap;
+use std::sync::mpsc;
+
+use crate::bidi;
+use crate::blog::{self, Post};
+use crate::components::a11y;
+use crate::config::{BlogConfig, Config};
+use crate::fetch::{Fetch, FetchResult};
+use crate::i18n::I18n;
+use crate::pages::{MarkdownPage, PageContent};
+
+/// The post index, fetched the first time the blog is shown
+#[derive(Default)]
+enum BlogIndex {
+    #[default]
+    NotLoaded,
+    Loading(mpsc::Receiver<FetchResult>),
+    Loaded(Vec<Post>),
+    Failed(String),
+}
+
+/// Blog index, opened posts and where the reader is
+#[derive(Default)]
+pub struct BlogState {
+    index: BlogIndex,
+    /// Markdown of each opened post, by slug
+    posts: HashMap<String, PageContent>,
+    /// Slug of the open post; the list is shown when `None`
+    pub selected: Option<String>,
+    /// Page of the list, from 0
+    page: usize,
+}
+
+impl BlogState {
+    /// Opens on `selected`, e.g. from a `#/blog/<post>` link
+    pub fn new(selected: Option<String>) -> Self {
+        Self {
+            selected,
+            ..Default::default()
+        }
+    }
+
+    /// Posts once the index has loaded, newest first
+    pub fn posts(&self) -> &[Post] {
+        match &self.index {
+            BlogIndex::Loaded(posts) => posts,
+            _ => &[],
+        }
+    }
+}
+
+pub struct BlogPage;
+
+impl BlogPage {
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        config: &Config,
+        i18n: &I18n,
+        fetcher: &dyn Fetch,
+        blog: &BlogConfig,
+        state: &mut BlogState,
+        markdown_cache: &mut CommonMarkCache,
+    ) {
+        Self::load_index(ctx, i18n, fetcher, blog, &mut state.index);
+
+        let posts = match &state.index {
+            BlogIndex::Loaded(posts) => posts,
+            BlogIndex::Failed(error) => {
+                Self::header(ui, i18n.blog_title(config));
+                ui.colored_label(ui.visuals().error_fg_color, error.as_str());
+                return;
+            }
+            _ => {
+                Self::header(ui, i18n.blog_title(config));
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(i18n.tr("blog-loading"));
+                });
+                return;
+            }
+        };
+
+        match &state.selected {
+            Some(slug) => {
+                let post = posts.iter().find(|post| &post.slug == slug);
+
+                let back_button = ui.button(i18n.tr("blog-all-posts"));
+                a11y::set_label(&back_button, i18n.label("blog-all-posts-label"));
+                ui.add_space(5.0);
+
+                match post {
+                    Some(post) => {
+                        ui.heading(bidi::visual(&post.title));
+                        if let Some(date) = post.display_date() {
+                            ui.label(egui::RichText::new(date).weak());
+                        }
+                        ui.add_space(5.0);
+                        ui.separator();
+                        ui.add_space(10.0);
+
+                        // Each post keeps its own scroll position
+                        let content = state.posts.entry(post.slug.clone()).or_default();
+                        ui.push_id(&post.slug, |ui| {
+                            MarkdownPage::show_fetched(ui, ctx, config, i18n, fetcher, &post.url, content, markdown_cache);
+                        });
+                    }
+                    None => {
+                        Self::header(ui, i18n.blog_title(config));
+                        ui.label(i18n.tr_args("blog-not-found", &[("slug", slug)]));
+                    }
+                }
+
+                if back_button.clicked() {
+                    state.selected = None;
+                }
+            }
+            None => {
+                Self::header(ui, i18n.blog_title(config));
+
+                if let Some(slug) = Self::render_list(ui, i18n, posts, blog.per_page.max(1), &mut state.page) {
+                    state.selected = Some(slug);
+                }
+            }
+        }
+    }
+
+    fn header(ui: &mut egui::Ui, title: &str) {
+        ui.heading(title);
+        ui.add_space(5.0);
+        ui.separator();
+        ui.add_space(10.0);
+    }
+
+    /// One page of the list; returns the slug of a post that was clicked
+    fn render_list(
+        ui: &mut egui::Ui,
+        i18n: &I18n,
+        posts: &[Post],
+        per_page: usize,
+        page: &mut usize,
+    ) -> Option<String> {
+        if posts.is_empty() {
+            ui.label(i18n.tr("blog-empty"));
+            return None;
+        }
+
+        let page_count = posts.len().div_ceil(per_page);
+        *page = (*page).min(page_count - 1);
+        let mut opened = None;
+
+        if page_count > 1 {
+            ui.horizontal(|ui| {
+                if ui.add_enabled(*page > 0, egui::Button::new(i18n.tr("blog-newer"))).clicked() {
+                    *page -= 1;
+                }
+                ui.label(i18n.tr_args("blog-page", &[("page", &(*page + 1)), ("count", &page_count)]));
+                if ui.add_enabled(*page + 1 < page_count, egui::Button::new(i18n.tr("blog-older"))).clicked() {
+                    *page += 1;
+                }
+            });
+            ui.add_space(10.0);
+        }
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for post in posts.iter().skip(*page * per_page).take(per_page) {
+                let title = ui.link(egui::RichText::new(bidi::visual(&post.title)).strong());
+                a11y::set_label(&title, &i18n.label_args("blog-read-label", &[("title", &post.title)]));
+                if title.clicked() {
+                    opened = Some(post.slug.clone());
+                }
+                if let Some(date) = post.display_date() {
+                    ui.label(egui::RichText::new(date).weak());
+                }
+                if let Some(summary) = &post.summary {
+                    bidi::label(ui, summary);
+                }
+                ui.add_space(5.0);
+                ui.separator();
+                ui.add_space(5.0);
+            }
+        });
+
+        opened
+    }
+
+    /// Start fetching on first view and pick up the response when it arrives
+    fn load_index(ctx: &egui::Context, i18n: &I18n, fetcher: &dyn Fetch, blog: &BlogConfig, index: &mut BlogIndex) {
+        match index {
+            BlogIndex::NotLoaded => {
+                *index = BlogIndex::Loading(fetcher.receive(&blog.index, Some(ctx)));
+            }
+            BlogIndex::Loading(receiver) => {
+                if let Ok(result) = receiver.try_recv() {
+                    *index = match result {
+                        Ok(response) if !response.ok => BlogIndex::Failed(i18n.tr_args(
+                            "load-failed",
+                            &[("url", &blog.index), ("status", &format!("{} {}", response.status, response.status_text))],
+                        )),
+                        Ok(response) => match response.text() {
+                            Some(text) => match blog::parse_index(text, &blog.index) {
+                                Ok(posts) => BlogIndex::Loaded(posts),
+                                Err(e) => BlogIndex::Failed(e),
+                            },
+                            None => BlogIndex::Failed(i18n.tr_args("read-failed", &[("url", &blog.index)])),
+                        },
+                        Err(e) => BlogIndex::Failed(i18n.tr_args("fetch-error", &[("error", &e)])),
+                    };
+                }
+            }
+            BlogIndex::Loaded(_) | BlogIndex::Failed(_) => {}
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use eframe::egui;
+use qrcode::{Color, QrCode};
+
+use crate::components::a11y;
+use crate::config::LinkConfig;
+use crate::i18n::I18n;
+
+/// Side of a QR code on the Links page
+const QR_SIZE: f32 = 96.0;
+
+/// Light modules around the code that scanners need to find it
+const QR_QUIET_ZONE: usize = 2;
+
+/// QR codes for `[[links]]`, encoded once at startup
+pub struct LinkCodes {
+    /// One per link; `None` when the URL is too long to encode
+    codes: Vec<Option<QrCode>>,
+}
+
+impl LinkCodes {
+    pub fn new(links: &[LinkConfig]) -> Self {
+        Self {
+            codes: links.iter().map(|link| QrCode::new(link.url.as_bytes()).ok()).collect(),
+        }
+    }
+}
+
+pub struct LinksPage;
+
+impl LinksPage {
+    pub fn render(ui: &mut egui::Ui, i18n: &I18n, links: &[LinkConfig], codes: &LinkCodes) {
+        ui.heading(i18n.tr("route-links"));
+        ui.add_space(5.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        // Groups in the order they first appear, ungrouped links first
+        let mut groups: Vec<Option<&str>> = Vec::new();
+        for link in links {
+            let group = link.group.as_deref();
+            if !groups.contains(&group) {
+                groups.push(group);
+            }
+        }
+        groups.sort_by_key(Option::is_some);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for group in groups {
+                if let Some(group) = group {
+                    ui.add_space(5.0);
+                    ui.strong(group);
+                    ui.add_space(5.0);
+                }
+
+                for (link, code) in links.iter().zip(&codes.codes) {
+                    if link.group.as_deref() != group {
+                        continue;
+                    }
+                    ui.horizontal(|ui| {
+                        if let Some(code) = code {
+                            Self::paint_qr(ui, i18n, code, &link.label);
+                        }
+                        ui.vertical(|ui| {
+                            ui.hyperlink_to(format!("{} {}", link.icon(), link.label), &link.url);
+                            ui.label(egui::RichText::new(&link.url).weak());
+                        });
+                    });
+                    ui.add_space(10.0);
+                }
+            }
+        });
+    }
+
+    /// Dark modules on a light square, whatever the theme, so phones can scan it
+    fn paint_qr(ui: &mut egui::Ui, i18n: &I18n, code: &QrCode, label: &str) {
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(QR_SIZE, QR_SIZE), egui::Sense::hover());
+        a11y::set_label(&response, &i18n.label_args("links-qr-label", &[("label", &label)]));
+
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, egui::Color32::WHITE);
+
+        let width = code.width();
+        let module = QR_SIZE / (width + 2 * QR_QUIET_ZONE) as f32;
+        let origin = rect.min + egui::vec2(module, module) * QR_QUIET_ZONE as f32;
+        for (idx, color) in code.to_colors().into_iter().enumerate() {
+            if color == Color::Dark {
+                let min = origin + egui::vec2((idx % width) as f32, (idx / width) as f32) * module;
+                painter.rect_filled(egui::Rect::from_min_size(min, egui::vec2(module, module)), 0.0, egui::Color32::BLACK);
+            }
+        }
+    }
+}
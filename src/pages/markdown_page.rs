@@ -0,0 +1,124 @@
+use eframe::egui;
+use egui_commonmark::CommonMarkCache;
+use std::sync::mpsc;
+
+use crate::bidi;
+use crate::config::{Config, PageConfig};
+use crate::fetch::{Fetch, FetchResult};
+use crate::i18n::I18n;
+use crate::links::LinkResolver;
+use crate::sanitize::Sanitizer;
+
+/// Markdown of a `url` page, fetched the first time it is shown
+#[derive(Default)]
+pub enum PageContent {
+    #[default]
+    NotLoaded,
+    Loading(mpsc::Receiver<FetchResult>),
+    Loaded(String),
+    Failed(String),
+}
+
+/// A `[[pages]]` entry rendered with `CommonMarkViewer`
+pub struct MarkdownPage;
+
+impl MarkdownPage {
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        config: &Config,
+        i18n: &I18n,
+        fetcher: &dyn Fetch,
+        page: &PageConfig,
+        content: &mut PageContent,
+        markdown_cache: &mut CommonMarkCache,
+    ) {
+        ui.heading(i18n.page_title(page));
+        ui.add_space(5.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        match i18n.page_source(page) {
+            (Some(markdown), _) => Self::show_markdown(ui, markdown_cache, markdown),
+            (None, Some(url)) => Self::show_fetched(ui, ctx, config, i18n, fetcher, url, content, markdown_cache),
+            (None, None) => {}
+        }
+    }
+
+    /// Markdown fetched from `url`, with a spinner until it arrives
+    #[allow(clippy::too_many_arguments)]
+    pub fn show_fetched(
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        config: &Config,
+        i18n: &I18n,
+        fetcher: &dyn Fetch,
+        url: &str,
+        content: &mut PageContent,
+        markdown_cache: &mut CommonMarkCache,
+    ) {
+        Self::load(ctx, config, i18n, fetcher, url, content);
+        match content {
+            PageContent::Loaded(markdown) => Self::show_markdown(ui, markdown_cache, markdown),
+            PageContent::Failed(error) => {
+                ui.colored_label(ui.visuals().error_fg_color, error.as_str());
+            }
+            _ => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(i18n.tr("loading"));
+                });
+            }
+        }
+    }
+
+    fn show_markdown(ui: &mut egui::Ui, markdown_cache: &mut CommonMarkCache, markdown: &str) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.style_mut().spacing.item_spacing.y = 8.0;
+            let visual = bidi::markdown_in(ui, markdown);
+            egui_commonmark::CommonMarkViewer::new()
+                .max_image_width(Some(800))
+                .show(ui, markdown_cache, visual.as_deref().unwrap_or(markdown));
+        });
+    }
+
+    /// Start fetching on first view and pick up the response when it arrives
+    fn load(
+        ctx: &egui::Context,
+        config: &Config,
+        i18n: &I18n,
+        fetcher: &dyn Fetch,
+        url: &str,
+        content: &mut PageContent,
+    ) {
+        match content {
+            PageContent::NotLoaded => {
+                *content = PageContent::Loading(fetcher.receive(url, Some(ctx)));
+            }
+            PageContent::Loading(receiver) => {
+                if let Ok(result) = receiver.try_recv() {
+                    *content = match result {
+                        Ok(response) if !response.ok => PageContent::Failed(i18n.tr_args(
+                            "load-failed",
+                            &[("url", &url), ("status", &format!("{} {}", response.status, response.status_text))],
+                        )),
+                        Ok(response) => match response.text() {
+                            Some(text) => {
+                                // Same link policy as the resume, resolved against the page's URL
+                                let sanitizer = Sanitizer::new(&config.sanitize, url);
+                                let links = LinkResolver::new(url, &config.app.website);
+                                let markdown = sanitizer.clean_markdown(text);
+                                let markdown = links.resolve_markdown(&markdown, sanitizer.rewrites_relative_links());
+                                PageContent::Loaded(markdown)
+                            }
+                            None => PageContent::Failed(i18n.tr_args("read-failed", &[("url", &url)])),
+                        },
+                        Err(e) => PageContent::Failed(i18n.tr_args("fetch-error", &[("error", &e)])),
+                    };
+                }
+            }
+            PageContent::Loaded(_) | PageContent::Failed(_) => {}
+        }
+    }
+}
@@ -1,9 +1,11 @@
-pub mod home;
 pub mod artworks;
+pub mod blog;
+pub mod links;
+pub mod markdown_page;
 pub mod resume;
-pub mod about;
 
-pub use home::HomePage;
 pub use artworks::ArtworksPage;
-pub use resume::ResumePage;
-pub use about::AboutPage;
+pub use blog::{BlogPage, BlogState};
+pub use links::{LinkCodes, LinksPage};
+pub use markdown_page::{MarkdownPage, PageContent};
+pub use resume::{PdfExport, ResumeLayout, ResumeNavigation, ResumePage, ResumeSearch};
@@ -1,42 +1,699 @@
-use eframe::egui;
+use eframe::egui::{self, accesskit::Role};
 use egui_commonmark::CommonMarkCache;
+use std::sync::mpsc;
 
+use crate::bidi;
+use crate::components::a11y;
 use crate::config::Config;
+use crate::fetch::{Fetch, FetchResult};
+use crate::i18n::I18n;
+use crate::markdown::{self, Heading};
+use crate::pdf;
+use crate::resume::{date_range, Basics, JsonResume, ResumeBlock, ResumeDocument};
+
+/// How far below the top of the view a heading may be and still count as current
+const ACTIVE_SECTION_MARGIN: f32 = 40.0;
+
+/// How far to the left of the view the resume is laid out a second time to
+/// find matches; nothing there is on screen or under the pointer
+const MATCH_LAYOUT_OFFSET: f32 = 100_000.0;
+
+/// Outline state: where to scroll to next and which section is on screen
+#[derive(Debug, Default)]
+pub struct ResumeNavigation {
+    /// Slug of a section to bring into view once it is rendered
+    pub scroll_to: Option<String>,
+    /// Slug of the section currently at the top of the view
+    pub active: Option<String>,
+}
+
+/// In-page find state
+#[derive(Debug, Default)]
+pub struct ResumeSearch {
+    pub query: String,
+    /// Index of the current match across the whole resume
+    current: usize,
+    /// Matches found when the resume was last laid out
+    total: usize,
+    /// Whether the current match still has to be brought into view
+    scroll_pending: bool,
+}
+
+impl ResumeSearch {
+    pub fn search_id() -> egui::Id {
+        egui::Id::new("resume_search")
+    }
+
+    fn select(&mut self, idx: usize) {
+        self.current = idx;
+        self.scroll_pending = true;
+    }
+
+    fn next(&mut self) {
+        if self.total > 0 {
+            self.select((self.current + 1) % self.total);
+        }
+    }
+
+    fn previous(&mut self) {
+        if self.total > 0 {
+            self.select((self.current + self.total - 1) % self.total);
+        }
+    }
+}
+
+/// Sections, outline and match positions of the resume, kept from frame to
+/// frame and worked out again only when what they depend on changes
+#[derive(Default)]
+pub struct ResumeLayout {
+    /// Bumped whenever the resume is replaced
+    generation: u64,
+    /// Generation and locale the sections and outline were made for
+    document_key: Option<(u64, String)>,
+    sections: Vec<markdown::Section>,
+    outline: Vec<Heading>,
+    matches_key: Option<MatchKey>,
+    /// Where the matches are, relative to the top left of the resume
+    matches: Vec<egui::Rect>,
+}
+
+/// What the match positions depend on
+#[derive(PartialEq)]
+struct MatchKey {
+    generation: u64,
+    query: String,
+    width: f32,
+    /// Height of the rendered resume, which changes as images load
+    height: f32,
+}
+
+impl ResumeLayout {
+    /// Forget everything worked out for the previous resume
+    pub fn document_changed(&mut self) {
+        self.generation += 1;
+    }
+
+    fn prepare(&mut self, document: &ResumeDocument, i18n: &I18n) {
+        let key = (self.generation, i18n.locale().to_string());
+        if self.document_key.as_ref() == Some(&key) {
+            return;
+        }
+        self.outline = document.outline(i18n);
+        self.sections = match document {
+            ResumeDocument::Markdown(markdown) => markdown::sections(markdown),
+            ResumeDocument::Json(_) => Vec::new(),
+        };
+        self.document_key = Some(key);
+        self.matches_key = None;
+    }
+}
+
+/// A PDF export waiting on the save dialog, or how the last one went
+#[derive(Default)]
+pub enum PdfExport {
+    #[default]
+    Idle,
+    /// Saving under the file name offered
+    Saving(String, mpsc::Receiver<Result<bool, String>>),
+    /// What to show next to the export button
+    Done(Result<String, String>),
+}
+
+impl PdfExport {
+    /// Pick up the outcome of the save dialog once it closes
+    fn poll(&mut self, i18n: &I18n) {
+        let PdfExport::Saving(file_name, receiver) = self else {
+            return;
+        };
+        if let Ok(result) = receiver.try_recv() {
+            *self = match result {
+                Ok(true) => PdfExport::Done(Ok(i18n.tr_args("resume-saved", &[("file", file_name)]))),
+                Ok(false) => PdfExport::Idle,
+                Err(error) => PdfExport::Done(Err(error)),
+            };
+        }
+    }
+}
 
 pub struct ResumePage;
 
 impl ResumePage {
+    /// Fetch the resume HTML; the response is picked up by `MeguiApp`
+    pub fn start_fetch(
+        ctx: &egui::Context,
+        config: &Config,
+        fetcher: &dyn Fetch,
+        resume_loading: &mut bool,
+        resume_receiver: &mut Option<mpsc::Receiver<FetchResult>>,
+    ) {
+        *resume_loading = true;
+        *resume_receiver = Some(fetcher.receive(&config.app.resume, Some(ctx)));
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         ui: &mut egui::Ui,
         ctx: &egui::Context,
         config: &Config,
-        resume_content: &Option<String>,
+        i18n: &I18n,
+        resume: &Option<ResumeDocument>,
         markdown_cache: &mut CommonMarkCache,
+        layout: &mut ResumeLayout,
+        navigation: &mut ResumeNavigation,
+        search: &mut ResumeSearch,
+        export: &mut PdfExport,
     ) {
-        ui.heading("Resume");
+        ui.heading(i18n.tr("route-resume"));
         ui.add_space(5.0);
         ui.separator();
         ui.add_space(10.0);
 
-        if ui.button("🔗 Open in New Tab").clicked() {
-            ctx.open_url(egui::OpenUrl::new_tab(&config.app.resume));
-        }
+        ui.horizontal(|ui| {
+            let new_tab_button = ui.button(i18n.tr("open-in-new-tab"));
+            a11y::set_label(&new_tab_button, i18n.label("resume-open-label"));
+            if new_tab_button.clicked() {
+                ctx.open_url(egui::OpenUrl::new_tab(&config.app.resume));
+            }
+
+            export.poll(i18n);
+            let saving = matches!(export, PdfExport::Saving(..));
+            let export_button =
+                ui.add_enabled(resume.is_some() && !saving, egui::Button::new(i18n.tr("resume-export")));
+            a11y::set_label(&export_button, i18n.label("resume-export-label"));
+            if export_button.clicked() {
+                if let Some(document) = resume {
+                    *export = Self::export_pdf(ctx, config, i18n, document);
+                }
+            }
+
+            match export {
+                PdfExport::Done(Ok(message)) => {
+                    ui.label(egui::RichText::new(message.as_str()).weak());
+                }
+                PdfExport::Done(Err(error)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, error.as_str());
+                }
+                PdfExport::Idle | PdfExport::Saving(..) => {}
+            }
+        });
 
         ui.add_space(10.0);
         ui.separator();
         ui.add_space(10.0);
 
+        let Some(document) = resume else {
+            ui.label(i18n.tr("resume-loading"));
+            return;
+        };
+
+        layout.prepare(document, i18n);
+        let ResumeLayout {
+            generation,
+            sections,
+            outline,
+            matches_key,
+            matches,
+            ..
+        } = layout;
+
+        // Outline stays put while the resume scrolls
+        Self::render_outline(ui, i18n, outline, navigation);
+        Self::render_search_bar(ui, i18n, search);
+
         egui::ScrollArea::vertical().show(ui, |ui| {
-            if let Some(markdown) = resume_content {
-                // Configure better spacing for markdown rendering
-                ui.style_mut().spacing.item_spacing.y = 8.0; // Space between elements
-
-                // Render markdown with proper formatting
-                egui_commonmark::CommonMarkViewer::new()
-                    .max_image_width(Some(800))
-                    .show(ui, markdown_cache, markdown);
-            } else {
-                ui.label("Loading resume...");
+            // Configure better spacing for markdown rendering
+            ui.style_mut().spacing.item_spacing.y = 8.0; // Space between elements
+
+            // Highlights are filled in once the matches have been found
+            let highlights = ui.painter().add(egui::Shape::Noop);
+            let content_rect = ui.max_rect();
+            let mut active = None;
+
+            Self::render_document(ui, i18n, document, sections, outline, markdown_cache, |ui, heading| {
+                Self::track_section(ui, heading, navigation, &mut active);
+            });
+            navigation.active = active;
+
+            let key = MatchKey {
+                generation: *generation,
+                query: search.query.trim().to_string(),
+                width: content_rect.width(),
+                height: ui.min_rect().height(),
+            };
+            if matches_key.as_ref() != Some(&key) {
+                *matches = if Self::may_contain(document, sections, &key.query) {
+                    Self::locate_matches(ui, content_rect, &key.query, |ui| {
+                        Self::render_document(ui, i18n, document, sections, outline, markdown_cache, |_, _| {});
+                    })
+                } else {
+                    Vec::new()
+                };
+                *matches_key = Some(key);
+            }
+
+            let origin = content_rect.min.to_vec2();
+            let on_screen: Vec<egui::Rect> = matches.iter().map(|rect| rect.translate(origin)).collect();
+            Self::highlight_matches(ui, highlights, &on_screen, search);
+        });
+    }
+
+    /// Render every section of the resume, calling `on_section` where each one starts
+    fn render_document(
+        ui: &mut egui::Ui,
+        i18n: &I18n,
+        document: &ResumeDocument,
+        sections: &[markdown::Section],
+        outline: &[Heading],
+        markdown_cache: &mut CommonMarkCache,
+        mut on_section: impl FnMut(&mut egui::Ui, Option<&Heading>),
+    ) {
+        match document {
+            ResumeDocument::Markdown(_) => {
+                for (idx, section) in sections.iter().enumerate() {
+                    on_section(ui, section.heading.as_ref());
+
+                    // Render markdown with proper formatting
+                    ui.push_id(idx, |ui| {
+                        let visual = bidi::markdown_in(ui, &section.markdown);
+                        egui_commonmark::CommonMarkViewer::new()
+                            .max_image_width(Some(800))
+                            .show(ui, markdown_cache, visual.as_deref().unwrap_or(&section.markdown));
+                    });
+                }
+            }
+            ResumeDocument::Json(resume) => {
+                for (block, heading) in resume.blocks().into_iter().zip(outline) {
+                    on_section(ui, Some(heading));
+                    JsonResumeView::render_block(ui, i18n, resume, block, &heading.text);
+                }
+            }
+        }
+    }
+
+    fn render_search_bar(ui: &mut egui::Ui, i18n: &I18n, search: &mut ResumeSearch) {
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut search.query)
+                    .id(ResumeSearch::search_id())
+                    .hint_text(i18n.tr("resume-find"))
+                    .desired_width(240.0),
+            );
+            a11y::set_label(&response, i18n.label("resume-find-label"));
+
+            if response.changed() {
+                search.select(0);
+            }
+            // Enter / Shift+Enter step through matches without leaving the field
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                if ui.input(|i| i.modifiers.shift) {
+                    search.previous();
+                } else {
+                    search.next();
+                }
+                response.request_focus();
+            }
+
+            let total = search.total;
+            let previous = ui.add_enabled(total > 0, egui::Button::new("⬆"));
+            a11y::set_label(&previous, i18n.label("resume-previous-match"));
+            if previous.clicked() {
+                search.previous();
+            }
+            let next = ui.add_enabled(total > 0, egui::Button::new("⬇"));
+            a11y::set_label(&next, i18n.label("resume-next-match"));
+            if next.clicked() {
+                search.next();
+            }
+
+            if !search.query.trim().is_empty() {
+                let status = if total == 0 {
+                    i18n.tr("no-matches").to_string()
+                } else {
+                    i18n.tr_args("resume-match-position", &[("current", &(search.current + 1)), ("total", &total)])
+                };
+                ui.label(egui::RichText::new(status).weak());
+            }
+        });
+        ui.add_space(5.0);
+    }
+
+    /// Whether the query can occur in the resume at all, which saves laying it
+    /// out a second time for queries that don't. JSON resumes are rendered from
+    /// fields rather than text, so those are always searched.
+    fn may_contain(document: &ResumeDocument, sections: &[markdown::Section], query: &str) -> bool {
+        if query.is_empty() {
+            return false;
+        }
+        match document {
+            ResumeDocument::Markdown(_) => sections.iter().any(|section| {
+                markdown::text_runs(&section.markdown)
+                    .iter()
+                    .any(|run| !markdown::find_matches(run, query).is_empty())
+            }),
+            ResumeDocument::Json(_) => true,
+        }
+    }
+
+    /// Rects of every match in document order, relative to the top left of
+    /// `content_rect`. Labels outside the view aren't painted, so the resume is
+    /// laid out again off to the side with nothing culled, and its text is
+    /// searched before it is thrown away. Only done when the query or layout
+    /// changes.
+    fn locate_matches(
+        ui: &mut egui::Ui,
+        content_rect: egui::Rect,
+        query: &str,
+        add_contents: impl FnOnce(&mut egui::Ui),
+    ) -> Vec<egui::Rect> {
+        let offset = egui::vec2(MATCH_LAYOUT_OFFSET, 0.0);
+        let mut hidden = ui.new_child(
+            egui::UiBuilder::new()
+                .id_salt("resume_search_layout")
+                .max_rect(content_rect.translate(-offset))
+                .disabled(),
+        );
+        hidden.set_clip_rect(egui::Rect::EVERYTHING);
+        a11y::set_hidden(&hidden);
+
+        let layer = hidden.layer_id();
+        let paint_len = || ui.ctx().graphics(|g| g.get(layer).map_or(0, |list| list.next_idx().0));
+        let first_shape = paint_len();
+        add_contents(&mut hidden);
+        let shapes = first_shape..paint_len();
+
+        ui.ctx().graphics_mut(|g| {
+            let list = g.entry(layer);
+            let mut rects = Vec::new();
+            for clipped in list.all_entries().skip(shapes.start).take(shapes.len()) {
+                collect_match_rects(&clipped.shape, query, &mut rects);
+            }
+            for idx in shapes {
+                list.reset_shape(egui::layers::ShapeIdx(idx));
+            }
+            let origin = content_rect.min.to_vec2();
+            rects.into_iter().map(|rect| rect.translate(offset - origin)).collect()
+        })
+    }
+
+    /// Highlight the matches and bring the current one into view
+    fn highlight_matches(
+        ui: &mut egui::Ui,
+        highlights: egui::layers::ShapeIdx,
+        matches: &[egui::Rect],
+        search: &mut ResumeSearch,
+    ) {
+        if search.total != matches.len() {
+            // The count in the search bar was drawn before the matches were found
+            search.total = matches.len();
+            ui.ctx().request_repaint();
+        }
+        search.current = search.current.min(search.total.saturating_sub(1));
+
+        let shapes = matches
+            .iter()
+            .enumerate()
+            .map(|(idx, rect)| {
+                let fill = if idx == search.current {
+                    ui.visuals().selection.bg_fill
+                } else {
+                    ui.visuals().selection.bg_fill.gamma_multiply(0.4)
+                };
+                egui::Shape::rect_filled(rect.expand(1.0), 2.0, fill)
+            })
+            .collect();
+        ui.painter().set(highlights, egui::Shape::Vec(shapes));
+
+        if search.scroll_pending {
+            if let Some(rect) = matches.get(search.current) {
+                ui.scroll_to_rect(*rect, Some(egui::Align::Center));
+            }
+            search.scroll_pending = false;
+        }
+    }
+
+    /// Render the resume to PDF and hand it to the platform to save
+    fn export_pdf(ctx: &egui::Context, config: &Config, i18n: &I18n, document: &ResumeDocument) -> PdfExport {
+        let title = format!("{} — {}", config.app.name, i18n.tr("route-resume"));
+        let fonts = pdf::PdfFonts::from_context(ctx);
+        match pdf::markdown_to_pdf(&title, &document.markdown(i18n), &fonts) {
+            Ok(bytes) => {
+                let file_name = format!("{}-resume.pdf", markdown::slugify(&config.app.name));
+                let receiver = pdf::save(ctx, &file_name, bytes);
+                PdfExport::Saving(file_name, receiver)
+            }
+            Err(error) => PdfExport::Done(Err(error)),
+        }
+    }
+
+    /// Record where a section starts: scroll to it if requested and work out
+    /// which section is at the top of the view
+    fn track_section(
+        ui: &mut egui::Ui,
+        heading: Option<&Heading>,
+        navigation: &mut ResumeNavigation,
+        active: &mut Option<String>,
+    ) {
+        let Some(heading) = heading else {
+            return;
+        };
+        let top = ui.cursor().top();
+
+        // A section counts as active once its heading reaches the top of the view
+        if top <= ui.clip_rect().top() + ACTIVE_SECTION_MARGIN || active.is_none() {
+            *active = Some(heading.slug.clone());
+        }
+
+        if navigation.scroll_to.as_deref() == Some(heading.slug.as_str()) {
+            let target = egui::Rect::from_min_size(
+                egui::pos2(ui.min_rect().left(), top),
+                egui::vec2(1.0, 1.0),
+            );
+            ui.scroll_to_rect(target, Some(egui::Align::TOP));
+            navigation.scroll_to = None;
+        }
+    }
+
+    fn render_outline(ui: &mut egui::Ui, i18n: &I18n, headings: &[Heading], navigation: &mut ResumeNavigation) {
+        if headings.is_empty() {
+            return;
+        }
+        let top_level = headings.iter().map(|h| h.level).min().unwrap_or(1);
+
+        // Opposite the menu, whichever side that is
+        let side = if i18n.is_rtl() { egui::panel::Side::Left } else { egui::panel::Side::Right };
+        egui::SidePanel::new(side, "resume_outline")
+            .resizable(true)
+            .default_width(220.0)
+            .show_inside(ui, |ui| {
+                a11y::set_role(ui, Role::Navigation, Some(i18n.label("resume-sections-label")));
+                ui.strong(i18n.tr("resume-contents"));
+                ui.add_space(5.0);
+
+                egui::ScrollArea::vertical()
+                    .id_salt("resume_outline_scroll")
+                    .show(ui, |ui| {
+                        for heading in headings {
+                            let selected = navigation.active.as_deref() == Some(heading.slug.as_str());
+                            ui.horizontal(|ui| {
+                                ui.add_space((heading.level - top_level) as f32 * 12.0);
+                                if ui.selectable_label(selected, bidi::visual(&heading.text)).clicked() {
+                                    navigation.scroll_to = Some(heading.slug.clone());
+                                }
+                            });
+                        }
+                    });
+            });
+    }
+}
+
+/// Screen rects of the query's occurrences in the text of a painted shape
+fn collect_match_rects(shape: &egui::Shape, query: &str, rects: &mut Vec<egui::Rect>) {
+    match shape {
+        egui::Shape::Vec(shapes) => {
+            for shape in shapes {
+                collect_match_rects(shape, query, rects);
+            }
+        }
+        egui::Shape::Text(text) => {
+            let galley = &text.galley;
+            for range in bidi::find_matches(galley.text(), query) {
+                let start = galley.pos_from_cursor(egui::text::CCursor::new(range.start));
+                let end = galley.pos_from_cursor(egui::text::CCursor::new(range.end));
+                let rect = if start.top() == end.top() {
+                    start.union(end)
+                } else {
+                    // Wrapped onto the next row: highlight up to the end of the first one
+                    egui::Rect::from_min_max(start.min, egui::pos2(galley.rect.right(), start.bottom()))
+                };
+                rects.push(rect.translate(text.pos.to_vec2()));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Native layout for JSON Resume documents
+struct JsonResumeView;
+
+impl JsonResumeView {
+    fn render_block(ui: &mut egui::Ui, i18n: &I18n, resume: &JsonResume, block: ResumeBlock, title: &str) {
+        if block == ResumeBlock::Basics {
+            ui.heading(bidi::visual(title));
+        } else {
+            ui.add_space(10.0);
+            ui.label(egui::RichText::new(bidi::visual(title)).heading().size(ui.text_style_height(&egui::TextStyle::Heading) * 0.8));
+            ui.separator();
+        }
+
+        match block {
+            ResumeBlock::Basics => Self::render_basics(ui, &resume.basics),
+            ResumeBlock::Work => {
+                for work in &resume.work {
+                    let title = match &work.position {
+                        Some(position) => format!("{} — {}", position, work.name),
+                        None => work.name.clone(),
+                    };
+                    Self::render_entry_title(ui, &title, &work.url);
+                    Self::render_dates(ui, i18n, &work.start_date, &work.end_date);
+                    if let Some(summary) = &work.summary {
+                        bidi::label(ui, summary);
+                    }
+                    Self::render_bullets(ui, &work.highlights);
+                    ui.add_space(5.0);
+                }
+            }
+            ResumeBlock::Education => {
+                for education in &resume.education {
+                    Self::render_entry_title(ui, &education.institution, &education.url);
+                    let degree = education.degree();
+                    if !degree.is_empty() {
+                        bidi::label(ui, degree);
+                    }
+                    Self::render_dates(ui, i18n, &education.start_date, &education.end_date);
+                    Self::render_bullets(ui, &education.courses);
+                    ui.add_space(5.0);
+                }
+            }
+            ResumeBlock::Skills => {
+                egui::Grid::new("resume_skills")
+                    .num_columns(2)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        for skill in &resume.skills {
+                            ui.vertical(|ui| {
+                                ui.strong(bidi::visual(&skill.name));
+                                if let Some(level) = &skill.level {
+                                    bidi::label(ui, egui::RichText::new(level).small().weak());
+                                }
+                            });
+                            Self::render_keywords(ui, &skill.keywords);
+                            ui.end_row();
+                        }
+                    });
+            }
+            ResumeBlock::Projects => {
+                for project in &resume.projects {
+                    Self::render_entry_title(ui, &project.name, &project.url);
+                    Self::render_dates(ui, i18n, &project.start_date, &project.end_date);
+                    if let Some(description) = &project.description {
+                        bidi::label(ui, description);
+                    }
+                    Self::render_bullets(ui, &project.highlights);
+                    Self::render_keywords(ui, &project.keywords);
+                    ui.add_space(5.0);
+                }
+            }
+            ResumeBlock::Languages => {
+                for language in &resume.languages {
+                    ui.horizontal(|ui| {
+                        ui.strong(bidi::visual(&language.language));
+                        if let Some(fluency) = &language.fluency {
+                            ui.label(bidi::visual(fluency));
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    fn render_basics(ui: &mut egui::Ui, basics: &Basics) {
+        if let Some(label) = &basics.label {
+            bidi::label(ui, egui::RichText::new(label).italics());
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            if let Some(email) = &basics.email {
+                ui.hyperlink_to(email, format!("mailto:{}", email));
+            }
+            if let Some(phone) = &basics.phone {
+                ui.label(phone);
+            }
+            if let Some(url) = &basics.url {
+                ui.hyperlink(url);
+            }
+            if let Some(location) = &basics.location {
+                ui.label(bidi::visual(&location.display()).into_owned());
+            }
+        });
+
+        if let Some(summary) = &basics.summary {
+            ui.add_space(5.0);
+            bidi::label(ui, summary);
+        }
+
+        if !basics.profiles.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                for profile in &basics.profiles {
+                    let name = profile.username.as_deref().unwrap_or(&profile.network);
+                    match &profile.url {
+                        Some(url) => {
+                            ui.hyperlink_to(format!("{}: {}", profile.network, name), url);
+                        }
+                        None => {
+                            ui.label(format!("{}: {}", profile.network, name));
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    fn render_entry_title(ui: &mut egui::Ui, title: &str, url: &Option<String>) {
+        match url {
+            Some(url) => {
+                ui.hyperlink_to(egui::RichText::new(bidi::visual(title)).strong(), url);
+            }
+            None => {
+                ui.strong(bidi::visual(title));
+            }
+        }
+    }
+
+    fn render_dates(ui: &mut egui::Ui, i18n: &I18n, start: &Option<String>, end: &Option<String>) {
+        if let Some(dates) = date_range(start, end, i18n) {
+            ui.label(egui::RichText::new(bidi::visual(&dates)).small().weak());
+        }
+    }
+
+    fn render_bullets(ui: &mut egui::Ui, items: &[String]) {
+        for item in items {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("•");
+                bidi::label(ui, item);
+            });
+        }
+    }
+
+    fn render_keywords(ui: &mut egui::Ui, keywords: &[String]) {
+        ui.horizontal_wrapped(|ui| {
+            for keyword in keywords {
+                egui::Frame::new()
+                    .fill(ui.visuals().faint_bg_color)
+                    .corner_radius(4.0)
+                    .inner_margin(egui::Margin::symmetric(6, 2))
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new(bidi::visual(keyword)).small());
+                    });
             }
         });
     }
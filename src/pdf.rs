@@ -0,0 +1,601 @@
+use eframe::egui;
+use printpdf::{IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::io::Cursor;
+use std::sync::mpsc;
+
+// A4 portrait with print margins, in millimetres
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+
+// Font sizes in points
+const BODY_SIZE: f32 = 10.5;
+const CODE_SIZE: f32 = 9.0;
+const FOOTER_SIZE: f32 = 8.0;
+const LINE_HEIGHT: f32 = 1.35;
+const LIST_INDENT: f32 = 6.0;
+
+const MM_PER_PT: f32 = 25.4 / 72.0;
+
+/// How long a download's object URL is kept after the link is clicked
+#[cfg(target_arch = "wasm32")]
+const REVOKE_DELAY_MS: i32 = 60_000;
+
+/// Font files for the PDF, in fallback order for each family
+#[derive(Clone, Default)]
+pub struct PdfFonts {
+    pub proportional: Vec<Vec<u8>>,
+    pub monospace: Vec<Vec<u8>>,
+}
+
+impl PdfFonts {
+    /// The fonts egui is currently using, so the PDF matches the app
+    pub fn from_context(ctx: &egui::Context) -> Self {
+        let definitions = ctx.fonts(|fonts| fonts.definitions().clone());
+        Self::from_definitions(&definitions)
+    }
+
+    pub fn from_definitions(definitions: &egui::FontDefinitions) -> Self {
+        let family = |family: egui::FontFamily| -> Vec<Vec<u8>> {
+            definitions
+                .families
+                .get(&family)
+                .into_iter()
+                .flatten()
+                .filter_map(|name| definitions.font_data.get(name))
+                .filter(|data| data.index == 0)
+                .filter(|data| ttf_parser::Face::parse(&data.font, 0).is_ok())
+                .map(|data| data.font.to_vec())
+                .collect()
+        };
+
+        Self {
+            proportional: family(egui::FontFamily::Proportional),
+            monospace: family(egui::FontFamily::Monospace),
+        }
+    }
+}
+
+/// Render markdown to a paginated A4 PDF
+pub fn markdown_to_pdf(title: &str, markdown: &str, fonts: &PdfFonts) -> Result<Vec<u8>, String> {
+    write_pdf(title, markdown, fonts).map(|(bytes, _)| bytes)
+}
+
+/// The PDF bytes and its number of pages
+fn write_pdf(title: &str, markdown: &str, fonts: &PdfFonts) -> Result<(Vec<u8>, usize), String> {
+    if fonts.proportional.is_empty() {
+        return Err("No usable font for PDF export".to_string());
+    }
+
+    let blocks = parse_blocks(markdown);
+
+    let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+    let layer = doc.get_page(page).get_layer(layer);
+
+    let proportional = FontChain::new(&fonts.proportional)?;
+    let monospace = if fonts.monospace.is_empty() {
+        FontChain::new(&fonts.proportional)?
+    } else {
+        FontChain::new(&fonts.monospace)?
+    };
+
+    let mut writer = PdfWriter {
+        doc,
+        layer,
+        page_number: 1,
+        y: PAGE_HEIGHT - MARGIN,
+        proportional,
+        monospace,
+    };
+
+    for block in &blocks {
+        writer.write_block(block)?;
+    }
+    writer.write_footer()?;
+
+    let pages = writer.page_number;
+    let bytes = writer.doc.save_to_bytes().map_err(|e| e.to_string())?;
+    Ok((bytes, pages))
+}
+
+/// Layout unit produced from the markdown
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    Text {
+        size: f32,
+        indent: f32,
+        prefix: Option<String>,
+        text: String,
+        space_before: f32,
+    },
+    Code(String),
+    Rule,
+}
+
+fn heading_size(level: HeadingLevel) -> f32 {
+    match level {
+        HeadingLevel::H1 => 20.0,
+        HeadingLevel::H2 => 15.0,
+        HeadingLevel::H3 => 12.5,
+        _ => 11.0,
+    }
+}
+
+/// Flatten markdown into blocks of plain text.
+/// Inline styling is dropped; link targets are kept in brackets since
+/// they can't be clicked on paper.
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut current: Option<Block> = None;
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut code: Option<String> = None;
+    let mut link_target: Option<String> = None;
+    let mut link_text = String::new();
+
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some(Block::Text {
+                    size: heading_size(level),
+                    indent: 0.0,
+                    prefix: None,
+                    text: String::new(),
+                    space_before: 4.0,
+                });
+            }
+            // Paragraphs inside list items continue the item
+            Event::Start(Tag::Paragraph | Tag::TableRow | Tag::TableHead) if current.is_none() => {
+                current = Some(Block::Text {
+                    size: BODY_SIZE,
+                    indent: lists.len() as f32 * LIST_INDENT,
+                    prefix: None,
+                    text: String::new(),
+                    space_before: 2.0,
+                });
+            }
+            Event::Start(Tag::List(start)) => {
+                if let Some(block) = current.take() {
+                    blocks.push(block);
+                }
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                if let Some(block) = current.take() {
+                    blocks.push(block);
+                }
+                let prefix = match lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}.", *number - 1)
+                    }
+                    _ => "•".to_string(),
+                };
+                current = Some(Block::Text {
+                    size: BODY_SIZE,
+                    indent: lists.len() as f32 * LIST_INDENT,
+                    prefix: Some(prefix),
+                    text: String::new(),
+                    space_before: 0.5,
+                });
+            }
+            Event::Start(Tag::CodeBlock(_)) => code = Some(String::new()),
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some(code) = code.take() {
+                    blocks.push(Block::Code(code.trim_end().to_string()));
+                }
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                link_target = Some(dest_url.to_string());
+                link_text.clear();
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some(target) = link_target.take() {
+                    let is_web = target.starts_with("http") || target.starts_with("mailto:");
+                    if is_web && link_text.trim() != target.trim_start_matches("mailto:") {
+                        push_text(&mut current, &format!(" ({})", target));
+                    }
+                }
+            }
+            Event::Start(Tag::Image { .. }) => push_text(&mut current, "[image: "),
+            Event::End(TagEnd::Image) => push_text(&mut current, "]"),
+            Event::End(TagEnd::TableCell) => push_text(&mut current, "  |  "),
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(code) = &mut code {
+                    code.push_str(&text);
+                } else {
+                    if link_target.is_some() {
+                        link_text.push_str(&text);
+                    }
+                    push_text(&mut current, &text);
+                }
+            }
+            Event::SoftBreak => push_text(&mut current, " "),
+            Event::HardBreak => push_text(&mut current, "\n"),
+            Event::Rule => blocks.push(Block::Rule),
+            Event::End(TagEnd::Heading(_))
+            | Event::End(TagEnd::Paragraph)
+            | Event::End(TagEnd::Item)
+            | Event::End(TagEnd::TableRow)
+            | Event::End(TagEnd::TableHead) => {
+                // Keep list items open across their inner paragraphs
+                let in_item = matches!(&current, Some(Block::Text { prefix: Some(_), .. }));
+                let ends_item = matches!(event, Event::End(TagEnd::Item));
+                if !in_item || ends_item {
+                    if let Some(mut block) = current.take() {
+                        if let Block::Text { text, .. } = &mut block {
+                            *text = text.trim_end_matches("  |  ").trim().to_string();
+                        }
+                        blocks.push(block);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(block) = current {
+        blocks.push(block);
+    }
+
+    blocks.retain(|block| match block {
+        Block::Text { text, prefix, .. } => !text.is_empty() || prefix.is_some(),
+        _ => true,
+    });
+    blocks
+}
+
+fn push_text(current: &mut Option<Block>, text: &str) {
+    let block = current.get_or_insert_with(|| Block::Text {
+        size: BODY_SIZE,
+        indent: 0.0,
+        prefix: None,
+        text: String::new(),
+        space_before: 2.0,
+    });
+    if let Block::Text { text: existing, .. } = block {
+        existing.push_str(text);
+    }
+}
+
+/// A family's fonts in fallback order, each embedded in the PDF on first use
+struct FontChain<'a> {
+    faces: Vec<(ttf_parser::Face<'a>, &'a [u8], Option<IndirectFontRef>)>,
+}
+
+impl<'a> FontChain<'a> {
+    fn new(fonts: &'a [Vec<u8>]) -> Result<Self, String> {
+        let faces = fonts
+            .iter()
+            .map(|bytes| {
+                ttf_parser::Face::parse(bytes, 0)
+                    .map(|face| (face, bytes.as_slice(), None))
+                    .map_err(|e| format!("Invalid font: {}", e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { faces })
+    }
+
+    /// First font in the chain that has a glyph for `c`
+    fn font_for(&self, c: char) -> usize {
+        self.faces
+            .iter()
+            .position(|(face, _, _)| face.glyph_index(c).is_some())
+            .unwrap_or(0)
+    }
+
+    /// Width of a character in millimetres
+    fn char_width(&self, c: char, size: f32) -> f32 {
+        let (face, _, _) = &self.faces[self.font_for(c)];
+        let advance = face
+            .glyph_index(c)
+            .and_then(|glyph| face.glyph_hor_advance(glyph))
+            .unwrap_or(face.units_per_em() / 2);
+        advance as f32 / face.units_per_em() as f32 * size * MM_PER_PT
+    }
+
+    fn text_width(&self, text: &str, size: f32) -> f32 {
+        text.chars().map(|c| self.char_width(c, size)).sum()
+    }
+
+    fn pdf_font(&mut self, doc: &PdfDocumentReference, idx: usize) -> Result<IndirectFontRef, String> {
+        let (_, bytes, font) = &mut self.faces[idx];
+        if font.is_none() {
+            let added = doc
+                .add_external_font(Cursor::new(bytes.to_vec()))
+                .map_err(|e| e.to_string())?;
+            *font = Some(added);
+        }
+        Ok(font.clone().expect("font was just added"))
+    }
+}
+
+struct PdfWriter<'a> {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    page_number: usize,
+    /// Baseline position of the next line, from the bottom of the page
+    y: f32,
+    proportional: FontChain<'a>,
+    monospace: FontChain<'a>,
+}
+
+impl PdfWriter<'_> {
+    fn write_block(&mut self, block: &Block) -> Result<(), String> {
+        match block {
+            Block::Text {
+                size,
+                indent,
+                prefix,
+                text,
+                space_before,
+            } => {
+                self.y -= space_before;
+                let x = MARGIN + indent;
+                let text_x = if prefix.is_some() { x + LIST_INDENT * 0.8 } else { x };
+                let lines = wrap(&self.proportional, text, *size, CONTENT_WIDTH - (text_x - MARGIN));
+
+                for (idx, line) in lines.iter().enumerate() {
+                    self.next_line(*size)?;
+                    if idx == 0 {
+                        if let Some(prefix) = prefix {
+                            self.draw_text(prefix, x, *size, false)?;
+                        }
+                    }
+                    self.draw_text(line, text_x, *size, false)?;
+                }
+                self.y -= size * MM_PER_PT * 0.4;
+            }
+            Block::Code(code) => {
+                self.y -= 2.0;
+                for line in code.lines() {
+                    for wrapped in wrap(&self.monospace, line, CODE_SIZE, CONTENT_WIDTH - LIST_INDENT) {
+                        self.next_line(CODE_SIZE)?;
+                        self.draw_text(&wrapped, MARGIN + LIST_INDENT, CODE_SIZE, true)?;
+                    }
+                }
+                self.y -= 2.0;
+            }
+            Block::Rule => {
+                self.y -= 3.0;
+                if self.y < MARGIN {
+                    self.new_page()?;
+                }
+                self.layer.add_line(Line {
+                    points: vec![
+                        (Point::new(Mm(MARGIN), Mm(self.y)), false),
+                        (Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(self.y)), false),
+                    ],
+                    is_closed: false,
+                });
+                self.y -= 3.0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move down one line, starting a new page if it doesn't fit
+    fn next_line(&mut self, size: f32) -> Result<(), String> {
+        let line_height = size * LINE_HEIGHT * MM_PER_PT;
+        if self.y - line_height < MARGIN {
+            self.new_page()?;
+        }
+        self.y -= line_height;
+        Ok(())
+    }
+
+    fn new_page(&mut self) -> Result<(), String> {
+        self.write_footer()?;
+        let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.page_number += 1;
+        self.y = PAGE_HEIGHT - MARGIN;
+        Ok(())
+    }
+
+    fn write_footer(&mut self) -> Result<(), String> {
+        let label = self.page_number.to_string();
+        let width = self.proportional.text_width(&label, FOOTER_SIZE);
+        let x = (PAGE_WIDTH - width) / 2.0;
+        let y = MARGIN / 2.0;
+        self.draw_text_at(&label, x, y, FOOTER_SIZE, false)
+    }
+
+    fn draw_text(&mut self, text: &str, x: f32, size: f32, mono: bool) -> Result<(), String> {
+        self.draw_text_at(text, x, self.y, size, mono)
+    }
+
+    /// Draw text split into runs by the font that covers each character
+    fn draw_text_at(&mut self, text: &str, x: f32, y: f32, size: f32, mono: bool) -> Result<(), String> {
+        let chain = if mono { &mut self.monospace } else { &mut self.proportional };
+
+        let mut runs: Vec<(usize, String)> = Vec::new();
+        for c in text.chars() {
+            let font = chain.font_for(c);
+            match runs.last_mut() {
+                Some((last, run)) if *last == font => run.push(c),
+                _ => runs.push((font, c.to_string())),
+            }
+        }
+
+        let mut x = x;
+        for (font, run) in runs {
+            let pdf_font = chain.pdf_font(&self.doc, font)?;
+            self.layer.use_text(run.as_str(), size, Mm(x), Mm(y), &pdf_font);
+            x += chain.text_width(&run, size);
+        }
+        Ok(())
+    }
+}
+
+/// Greedy word wrap to the given width in millimetres
+fn wrap(chain: &FontChain, text: &str, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut line_width = 0.0;
+
+        for word in paragraph.split_whitespace() {
+            let word_width = chain.text_width(word, size);
+            let space_width = if line.is_empty() { 0.0 } else { chain.char_width(' ', size) };
+
+            if !line.is_empty() && line_width + space_width + word_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0.0;
+            }
+
+            // Words longer than a whole line are broken by character
+            if word_width > width {
+                for c in word.chars() {
+                    let c_width = chain.char_width(c, size);
+                    if line_width + c_width > width && !line.is_empty() {
+                        lines.push(std::mem::take(&mut line));
+                        line_width = 0.0;
+                    }
+                    line.push(c);
+                    line_width += c_width;
+                }
+                continue;
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+                line_width += chain.char_width(' ', size);
+            }
+            line.push_str(word);
+            line_width += word_width;
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Save the PDF through a native file dialog, which runs on its own thread so
+/// the UI keeps drawing. Sends `Ok(false)` if the dialog was cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(ctx: &egui::Context, file_name: &str, bytes: Vec<u8>) -> mpsc::Receiver<Result<bool, String>> {
+    let (sender, receiver) = mpsc::channel();
+    let ctx = ctx.clone();
+    let file_name = file_name.to_string();
+    std::thread::spawn(move || {
+        let dialog = rfd::AsyncFileDialog::new()
+            .set_file_name(file_name)
+            .add_filter("PDF", &["pdf"])
+            .save_file();
+        let result = match pollster::block_on(dialog) {
+            Some(file) => std::fs::write(file.path(), &bytes)
+                .map(|()| true)
+                .map_err(|e| format!("Failed to write {}: {}", file.path().display(), e)),
+            None => Ok(false),
+        };
+        let _ = sender.send(result);
+        ctx.request_repaint();
+    });
+    receiver
+}
+
+/// Save the PDF as a browser download
+#[cfg(target_arch = "wasm32")]
+pub fn save(_ctx: &egui::Context, file_name: &str, bytes: Vec<u8>) -> mpsc::Receiver<Result<bool, String>> {
+    let (sender, receiver) = mpsc::channel();
+    let _ = sender.send(download(file_name, &bytes).map(|()| true));
+    receiver
+}
+
+#[cfg(target_arch = "wasm32")]
+fn download(file_name: &str, bytes: &[u8]) -> Result<(), String> {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let js_error = |e: wasm_bindgen::JsValue| format!("{:?}", e);
+
+    let parts = js_sys::Array::new();
+    parts.push(&js_sys::Uint8Array::from(bytes));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("application/pdf");
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options).map_err(js_error)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(js_error)?;
+
+    let window = web_sys::window().ok_or("No window to download into")?;
+    let document = window.document().ok_or("No document to download into")?;
+    let anchor: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(|_| "Failed to create download link")?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    // Some browsers start the download after the click returns, so the URL
+    // has to outlive it for a while
+    let revoke = Closure::once_into_js(move || {
+        let _ = web_sys::Url::revoke_object_url(&url);
+    });
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), REVOKE_DELAY_MS)
+        .map_err(js_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fonts() -> PdfFonts {
+        PdfFonts::from_definitions(&egui::FontDefinitions::default())
+    }
+
+    #[test]
+    fn renders_a_pdf_with_default_fonts() {
+        let markdown = "# Jane Doe\n\nSoftware engineer.\n\n## Work\n\n- Built things\n- Shipped things\n";
+        let bytes = markdown_to_pdf("Resume", markdown, &fonts()).unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn long_documents_span_several_pages() {
+        let markdown = "A paragraph of resume text that goes on for a while.\n\n".repeat(200);
+        let (_, pages) = write_pdf("Resume", &markdown, &fonts()).unwrap();
+        assert!(pages > 1);
+    }
+
+    #[test]
+    fn lists_keep_their_markers_and_links_show_targets() {
+        let blocks = parse_blocks("1. First [site](https://example.com)\n2. Second\n\n- Bullet\n");
+        let texts: Vec<(Option<String>, String)> = blocks
+            .into_iter()
+            .filter_map(|block| match block {
+                Block::Text { prefix, text, .. } => Some((prefix, text)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                (Some("1.".to_string()), "First site (https://example.com)".to_string()),
+                (Some("2.".to_string()), "Second".to_string()),
+                (Some("•".to_string()), "Bullet".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn wrapping_respects_the_width() {
+        let fonts = fonts();
+        let chain = FontChain::new(&fonts.proportional).unwrap();
+        let text = "word ".repeat(100);
+        let lines = wrap(&chain, &text, BODY_SIZE, CONTENT_WIDTH);
+        assert!(lines.len() > 1);
+        for line in lines {
+            assert!(chain.text_width(&line, BODY_SIZE) <= CONTENT_WIDTH);
+        }
+    }
+}
@@ -0,0 +1,489 @@
+use serde::Deserialize;
+use std::borrow::Cow;
+
+use crate::i18n::I18n;
+use crate::links::LinkResolver;
+use crate::markdown::{self, Heading};
+use crate::sanitize::Sanitizer;
+
+/// How the document at `config.app.resume` is interpreted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResumeFormat {
+    /// A web page, sanitized and converted to markdown
+    Html,
+    /// Markdown rendered as-is
+    Markdown,
+    /// A JSON Resume document (https://jsonresume.org/schema)
+    JsonResume,
+}
+
+impl ResumeFormat {
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => ResumeFormat::Markdown,
+            "json" | "jsonresume" | "json_resume" => ResumeFormat::JsonResume,
+            _ => ResumeFormat::Html,
+        }
+    }
+}
+
+/// A loaded resume, ready to render
+#[derive(Debug, Clone)]
+pub enum ResumeDocument {
+    Markdown(String),
+    /// Rendered natively with `JsonResumeView`
+    Json(Box<JsonResume>),
+}
+
+impl ResumeDocument {
+    pub fn parse(
+        format: ResumeFormat,
+        body: &str,
+        sanitizer: &Sanitizer,
+        links: &LinkResolver,
+    ) -> Result<Self, String> {
+        let rewrite_links = sanitizer.rewrites_relative_links();
+        match format {
+            ResumeFormat::Html => {
+                // Sanitize before converting, then drop any links the conversion let through
+                let cleaned_html = sanitizer.clean_html(body);
+                let markdown = sanitizer.clean_markdown(&html2md::parse_html(&cleaned_html));
+                Ok(ResumeDocument::Markdown(links.resolve_markdown(&markdown, rewrite_links)))
+            }
+            ResumeFormat::Markdown => {
+                let markdown = sanitizer.clean_markdown(body);
+                Ok(ResumeDocument::Markdown(links.resolve_markdown(&markdown, rewrite_links)))
+            }
+            ResumeFormat::JsonResume => {
+                let mut resume: JsonResume =
+                    serde_json::from_str(body).map_err(|e| format!("Invalid JSON Resume: {}", e))?;
+                resume.clean_urls(sanitizer, links);
+                Ok(ResumeDocument::Json(Box::new(resume)))
+            }
+        }
+    }
+
+    /// Markdown version of the document, e.g. for export
+    pub fn markdown(&self, i18n: &I18n) -> Cow<'_, str> {
+        match self {
+            ResumeDocument::Markdown(markdown) => Cow::Borrowed(markdown),
+            ResumeDocument::Json(resume) => Cow::Owned(resume.to_markdown(i18n)),
+        }
+    }
+
+    /// Headings shown in the outline, with the slugs used for deep links
+    pub fn outline(&self, i18n: &I18n) -> Vec<Heading> {
+        match self {
+            ResumeDocument::Markdown(markdown) => markdown::headings(markdown)
+                .into_iter()
+                .filter(|h| h.level <= markdown::OUTLINE_MAX_LEVEL)
+                .collect(),
+            ResumeDocument::Json(resume) => resume
+                .blocks()
+                .into_iter()
+                .map(|block| Heading {
+                    level: if block == ResumeBlock::Basics { 1 } else { 2 },
+                    text: resume.block_title(block, i18n).to_string(),
+                    slug: resume.block_slug(block),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct JsonResume {
+    pub basics: Basics,
+    pub work: Vec<Work>,
+    pub education: Vec<Education>,
+    pub skills: Vec<Skill>,
+    pub projects: Vec<Project>,
+    pub languages: Vec<Language>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Basics {
+    pub name: String,
+    pub label: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub url: Option<String>,
+    pub summary: Option<String>,
+    pub location: Option<Location>,
+    pub profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Location {
+    pub city: Option<String>,
+    pub region: Option<String>,
+    pub country_code: Option<String>,
+}
+
+impl Location {
+    pub fn display(&self) -> String {
+        [&self.city, &self.region, &self.country_code]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Profile {
+    pub network: String,
+    pub username: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Work {
+    pub name: String,
+    pub position: Option<String>,
+    pub url: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub summary: Option<String>,
+    pub highlights: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Education {
+    pub institution: String,
+    pub url: Option<String>,
+    pub area: Option<String>,
+    pub study_type: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub score: Option<String>,
+    pub courses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Skill {
+    pub name: String,
+    pub level: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Project {
+    pub name: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    pub highlights: Vec<String>,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Language {
+    pub language: String,
+    pub fluency: Option<String>,
+}
+
+/// Top-level parts of a JSON Resume, each shown under its own heading
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResumeBlock {
+    Basics,
+    Work,
+    Education,
+    Skills,
+    Projects,
+    Languages,
+}
+
+impl JsonResume {
+    /// Non-empty blocks in display order
+    pub fn blocks(&self) -> Vec<ResumeBlock> {
+        let mut blocks = vec![ResumeBlock::Basics];
+        if !self.work.is_empty() {
+            blocks.push(ResumeBlock::Work);
+        }
+        if !self.education.is_empty() {
+            blocks.push(ResumeBlock::Education);
+        }
+        if !self.skills.is_empty() {
+            blocks.push(ResumeBlock::Skills);
+        }
+        if !self.projects.is_empty() {
+            blocks.push(ResumeBlock::Projects);
+        }
+        if !self.languages.is_empty() {
+            blocks.push(ResumeBlock::Languages);
+        }
+        blocks
+    }
+
+    /// Heading of a block, in reading order
+    pub fn block_title<'a>(&'a self, block: ResumeBlock, i18n: &'a I18n) -> &'a str {
+        match block {
+            ResumeBlock::Basics if !self.basics.name.is_empty() => &self.basics.name,
+            ResumeBlock::Basics => i18n.label("resume-about"),
+            ResumeBlock::Work => i18n.label("resume-work"),
+            ResumeBlock::Education => i18n.label("resume-education"),
+            ResumeBlock::Skills => i18n.label("resume-skills"),
+            ResumeBlock::Projects => i18n.label("resume-projects"),
+            ResumeBlock::Languages => i18n.label("resume-languages"),
+        }
+    }
+
+    /// Deep link slug of a block, the same in every locale
+    pub fn block_slug(&self, block: ResumeBlock) -> String {
+        match block {
+            ResumeBlock::Basics if !self.basics.name.is_empty() => markdown::slugify(&self.basics.name),
+            ResumeBlock::Basics => "about".to_string(),
+            ResumeBlock::Work => "work".to_string(),
+            ResumeBlock::Education => "education".to_string(),
+            ResumeBlock::Skills => "skills".to_string(),
+            ResumeBlock::Projects => "projects".to_string(),
+            ResumeBlock::Languages => "languages".to_string(),
+        }
+    }
+
+    /// Clear links whose scheme the sanitize policy doesn't allow, and make
+    /// relative ones absolute
+    fn clean_urls(&mut self, sanitizer: &Sanitizer, links: &LinkResolver) {
+        let urls = std::iter::once(&mut self.basics.url)
+            .chain(self.basics.profiles.iter_mut().map(|p| &mut p.url))
+            .chain(self.work.iter_mut().map(|w| &mut w.url))
+            .chain(self.education.iter_mut().map(|e| &mut e.url))
+            .chain(self.projects.iter_mut().map(|p| &mut p.url));
+        for url in urls {
+            *url = url
+                .take()
+                .filter(|u| sanitizer.is_allowed_url(u))
+                .map(|u| links.resolve(&u));
+        }
+    }
+
+    /// Markdown with one heading per block, in the same order as `blocks`
+    pub fn to_markdown(&self, i18n: &I18n) -> String {
+        let mut md = String::new();
+
+        for block in self.blocks() {
+            let level = if block == ResumeBlock::Basics { "#" } else { "##" };
+            md.push_str(&format!("{} {}\n\n", level, self.block_title(block, i18n)));
+
+            match block {
+                ResumeBlock::Basics => {
+                    let basics = &self.basics;
+                    if let Some(label) = &basics.label {
+                        md.push_str(&format!("*{}*\n\n", label));
+                    }
+                    let location = basics.location.as_ref().map(Location::display);
+                    let contact: Vec<&str> = [&basics.email, &basics.phone, &basics.url, &location]
+                        .into_iter()
+                        .flatten()
+                        .map(String::as_str)
+                        .filter(|s| !s.is_empty())
+                        .collect();
+                    if !contact.is_empty() {
+                        md.push_str(&format!("{}\n\n", contact.join(" · ")));
+                    }
+                    if let Some(summary) = &basics.summary {
+                        md.push_str(&format!("{}\n\n", summary));
+                    }
+                    for profile in &basics.profiles {
+                        let name = profile.username.as_deref().unwrap_or(&profile.network);
+                        match &profile.url {
+                            Some(url) => md.push_str(&format!("- {}: [{}]({})\n", profile.network, name, url)),
+                            None => md.push_str(&format!("- {}: {}\n", profile.network, name)),
+                        }
+                    }
+                }
+                ResumeBlock::Work => {
+                    for work in &self.work {
+                        let title = match &work.position {
+                            Some(position) => format!("{} — {}", position, work.name),
+                            None => work.name.clone(),
+                        };
+                        md.push_str(&format!("**{}**\n\n", title));
+                        if let Some(dates) = date_range(&work.start_date, &work.end_date, i18n) {
+                            md.push_str(&format!("*{}*\n\n", dates));
+                        }
+                        if let Some(summary) = &work.summary {
+                            md.push_str(&format!("{}\n\n", summary));
+                        }
+                        push_list(&mut md, &work.highlights);
+                    }
+                }
+                ResumeBlock::Education => {
+                    for education in &self.education {
+                        md.push_str(&format!("**{}**\n\n", education.institution));
+                        let degree = education.degree();
+                        if !degree.is_empty() {
+                            md.push_str(&format!("{}\n\n", degree));
+                        }
+                        if let Some(dates) = date_range(&education.start_date, &education.end_date, i18n) {
+                            md.push_str(&format!("*{}*\n\n", dates));
+                        }
+                        push_list(&mut md, &education.courses);
+                    }
+                }
+                ResumeBlock::Skills => {
+                    for skill in &self.skills {
+                        let mut line = format!("- **{}**", skill.name);
+                        if let Some(level) = &skill.level {
+                            line.push_str(&format!(" ({})", level));
+                        }
+                        if !skill.keywords.is_empty() {
+                            line.push_str(&format!(": {}", skill.keywords.join(", ")));
+                        }
+                        md.push_str(&line);
+                        md.push('\n');
+                    }
+                    md.push('\n');
+                }
+                ResumeBlock::Projects => {
+                    for project in &self.projects {
+                        md.push_str(&format!("**{}**\n\n", project.name));
+                        if let Some(description) = &project.description {
+                            md.push_str(&format!("{}\n\n", description));
+                        }
+                        push_list(&mut md, &project.highlights);
+                    }
+                }
+                ResumeBlock::Languages => {
+                    for language in &self.languages {
+                        match &language.fluency {
+                            Some(fluency) => md.push_str(&format!("- {}: {}\n", language.language, fluency)),
+                            None => md.push_str(&format!("- {}\n", language.language)),
+                        }
+                    }
+                    md.push('\n');
+                }
+            }
+        }
+
+        md
+    }
+}
+
+impl Education {
+    /// e.g. "Bachelor, Computer Science (3.8)", or just the parts it has
+    pub fn degree(&self) -> String {
+        let present = |part: &Option<String>| part.clone().filter(|part| !part.trim().is_empty());
+        let degree = [&self.study_type, &self.area]
+            .into_iter()
+            .filter_map(present)
+            .collect::<Vec<_>>()
+            .join(", ");
+        match present(&self.score) {
+            Some(score) if degree.is_empty() => score,
+            Some(score) => format!("{} ({})", degree, score),
+            None => degree,
+        }
+    }
+}
+
+/// "2019-01 – 2021-06", "2021-07 – Present", or None without a start date
+pub fn date_range(start: &Option<String>, end: &Option<String>, i18n: &I18n) -> Option<String> {
+    let start = start.as_ref()?;
+    let end = end.as_deref().unwrap_or_else(|| i18n.label("resume-present"));
+    Some(format!("{} – {}", start, end))
+}
+
+fn push_list(md: &mut String, items: &[String]) {
+    for item in items {
+        md.push_str(&format!("- {}\n", item));
+    }
+    if !items.is_empty() {
+        md.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn block_titles_are_translated_but_slugs_are_not() {
+        let resume: JsonResume =
+            serde_json::from_str(r#"{"work": [{"name": "Acme", "startDate": "2021-07"}]}"#).unwrap();
+        let document = ResumeDocument::Json(Box::new(resume));
+        let french = I18n::new("fr", &Config::default());
+
+        let outline = document.outline(&french);
+        let titles: Vec<(&str, &str)> = outline.iter().map(|h| (h.text.as_str(), h.slug.as_str())).collect();
+        assert_eq!(titles, [("À propos", "about"), ("Expérience", "work")]);
+        assert!(document.markdown(&french).contains("*2021-07 – Aujourd'hui*"));
+    }
+
+    #[test]
+    fn degrees_join_the_parts_they_have() {
+        let education = |study_type: Option<&str>, area: Option<&str>, score: Option<&str>| Education {
+            study_type: study_type.map(str::to_string),
+            area: area.map(str::to_string),
+            score: score.map(str::to_string),
+            ..Default::default()
+        };
+        assert_eq!(education(Some("Bachelor"), Some("Physics"), Some("3.8")).degree(), "Bachelor, Physics (3.8)");
+        assert_eq!(education(None, Some("Physics"), None).degree(), "Physics");
+        assert_eq!(education(None, None, Some("3.8")).degree(), "3.8");
+        assert_eq!(education(Some(""), None, None).degree(), "");
+    }
+
+    #[test]
+    fn json_resumes_parse_with_missing_fields() {
+        let body = r#"{
+            "basics": {"name": "Ada Lovelace", "label": "Mathematician", "location": {"city": "London", "countryCode": "GB"},
+                       "profiles": [{"network": "GitHub", "username": "ada", "url": "https://github.com/ada"}]},
+            "work": [{"name": "Analytical Engine", "position": "Programmer", "startDate": "1842", "endDate": "1843",
+                      "highlights": ["First published program"]}],
+            "skills": [{"name": "Mathematics", "level": "Master", "keywords": ["Calculus", "Bernoulli numbers"]}],
+            "languages": [{"language": "English", "fluency": "Native"}],
+            "unknownSection": [1, 2, 3]
+        }"#;
+        let resume: JsonResume = serde_json::from_str(body).unwrap();
+        assert_eq!(resume.basics.location.as_ref().unwrap().display(), "London, GB");
+        assert_eq!(resume.work[0].start_date.as_deref(), Some("1842"));
+        assert!(resume.education.is_empty() && resume.projects.is_empty());
+        assert_eq!(
+            resume.blocks(),
+            [ResumeBlock::Basics, ResumeBlock::Work, ResumeBlock::Skills, ResumeBlock::Languages]
+        );
+    }
+
+    #[test]
+    fn json_resumes_convert_to_markdown() {
+        let resume = JsonResume {
+            basics: Basics {
+                name: "Ada Lovelace".to_string(),
+                label: Some("Mathematician".to_string()),
+                email: Some("ada@example.com".to_string()),
+                ..Default::default()
+            },
+            education: vec![Education {
+                institution: "University of London".to_string(),
+                study_type: Some("Private tutoring".to_string()),
+                area: Some("Mathematics".to_string()),
+                start_date: Some("1840".to_string()),
+                courses: vec!["Calculus".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let english = I18n::new("en", &Config::default());
+        assert_eq!(
+            resume.to_markdown(&english),
+            "# Ada Lovelace\n\n*Mathematician*\n\nada@example.com\n\n\
+             ## Education\n\n**University of London**\n\nPrivate tutoring, Mathematics\n\n*1840 – Present*\n\n\
+             - Calculus\n\n"
+        );
+    }
+}
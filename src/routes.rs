@@ -1,73 +1,205 @@
+use crate::config::Config;
+use crate::i18n::I18n;
+
+/// Sidebar positions of the built-in pages; `[[pages]]` entries are placed around them
+const ARTWORKS_POSITION: i32 = 20;
+const RESUME_POSITION: i32 = 30;
+const LINKS_POSITION: i32 = 50;
+
 /// Application routes/views
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Route {
-    Home,
+    /// A markdown page from `[[pages]]`, by index in the config
+    Page(usize),
     Artworks,
     Resume,
-    About,
+    /// Posts from `[blog]`; shown only when it is configured
+    Blog,
+    /// Everything in `[[links]]`; shown only when there are some
+    Links,
 }
 
 impl Route {
-    #[cfg(target_arch = "wasm32")]
-    pub fn to_hash(&self) -> &'static str {
+    /// Every route in sidebar order
+    pub fn all(config: &Config) -> Vec<Route> {
+        let mut routes: Vec<Route> = (0..config.pages.len())
+            .map(Route::Page)
+            .chain([Route::Artworks, Route::Resume])
+            .chain(config.blog.as_ref().map(|_| Route::Blog))
+            .chain((!config.links.is_empty()).then_some(Route::Links))
+            .collect();
+        routes.sort_by_key(|route| route.position(config));
+        routes
+    }
+
+    /// The `home` page, or whatever comes first in the sidebar
+    pub fn home(config: &Config) -> Route {
+        Self::from_slug("home", config).unwrap_or_else(|| Self::all(config)[0])
+    }
+
+    pub fn from_slug(slug: &str, config: &Config) -> Option<Route> {
+        match slug {
+            "artworks" => Some(Route::Artworks),
+            "resume" => Some(Route::Resume),
+            "blog" if config.blog.is_some() => Some(Route::Blog),
+            "links" if !config.links.is_empty() => Some(Route::Links),
+            _ => config.pages.iter().position(|page| page.slug == slug).map(Route::Page),
+        }
+    }
+
+    fn position(&self, config: &Config) -> i32 {
         match self {
-            Route::Home => "#/home",
-            Route::Artworks => "#/artworks",
-            Route::Resume => "#/resume",
-            Route::About => "#/about",
+            Route::Page(idx) => config.pages[*idx].position,
+            Route::Artworks => ARTWORKS_POSITION,
+            Route::Resume => RESUME_POSITION,
+            Route::Blog => config.blog.as_ref().map_or(0, |blog| blog.position),
+            Route::Links => LINKS_POSITION,
         }
     }
 
     #[cfg(target_arch = "wasm32")]
-    pub fn from_hash(hash: &str) -> Option<Self> {
-        match hash {
-            "#/home" | "#home" | "#/" | "#" | "" => Some(Route::Home),
-            "#/artworks" | "#artworks" => Some(Route::Artworks),
-            "#/resume" | "#resume" => Some(Route::Resume),
-            "#/about" | "#about" => Some(Route::About),
-            _ => None,
+    pub fn to_hash(&self, config: &Config) -> String {
+        match self {
+            Route::Page(idx) => format!("#/{}", config.pages[*idx].slug),
+            Route::Artworks => "#/artworks".to_string(),
+            Route::Resume => "#/resume".to_string(),
+            Route::Blog => "#/blog".to_string(),
+            Route::Links => "#/links".to_string(),
+        }
+    }
+
+    /// `#/<slug>` or `#<slug>`, with an optional `/<section>`; empty means home
+    pub fn from_hash(hash: &str, config: &Config) -> Option<Self> {
+        let (hash, _) = Self::split_section(hash);
+        let slug = hash.trim_start_matches('#').trim_start_matches('/');
+        if slug.is_empty() {
+            return Some(Self::home(config));
+        }
+        Self::from_slug(slug, config)
+    }
+
+    /// Split `#/resume/<section>` into `#/resume` and the section (or `#/blog/<post>`)
+    pub fn split_section(hash: &str) -> (&str, Option<&str>) {
+        let prefix = if hash.starts_with("#/") { 2 } else { 0 };
+        match hash[prefix..].find('/') {
+            Some(idx) => {
+                let (route, section) = hash.split_at(prefix + idx);
+                let section = &section[1..];
+                (route, (!section.is_empty()).then_some(section))
+            }
+            None => (hash, None),
+        }
+    }
+
+    /// Section part of the URL hash, e.g. the heading slug in `#/resume/<slug>`
+    /// or the post slug in `#/blog/<slug>`
+    pub fn get_section_from_url() -> Option<String> {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let hash = web_sys::window()?.location().hash().ok()?;
+            let (_, section) = Self::split_section(&hash);
+            // Non-ASCII slugs come back percent-encoded
+            let section = section?;
+            Some(js_sys::decode_uri_component(section).map_or_else(|_| section.to_string(), String::from))
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            None
         }
     }
 
-    pub fn get_from_url() -> Self {
+    pub fn get_from_url(config: &Config) -> Self {
         #[cfg(target_arch = "wasm32")]
         {
             if let Some(window) = web_sys::window() {
                 if let Ok(location) = window.location().hash() {
-                    if let Some(route) = Self::from_hash(&location) {
+                    if let Some(route) = Self::from_hash(&location, config) {
                         return route;
                     }
                 }
             }
         }
-        Route::Home
+        Self::home(config)
+    }
+
+    pub fn title<'a>(&self, config: &'a Config, i18n: &'a I18n) -> &'a str {
+        match self {
+            Route::Page(idx) => i18n.page_title(&config.pages[*idx]),
+            Route::Artworks => i18n.tr("route-artworks"),
+            Route::Resume => i18n.tr("route-resume"),
+            Route::Blog => i18n.blog_title(config),
+            Route::Links => i18n.tr("route-links"),
+        }
     }
 
-    pub fn title(&self) -> &'static str {
+    /// The title in reading order, for screen readers
+    pub fn label<'a>(&self, config: &'a Config, i18n: &'a I18n) -> &'a str {
         match self {
-            Route::Home => "Home",
-            Route::Artworks => "Artworks",
-            Route::Resume => "Resume",
-            Route::About => "About",
+            Route::Page(idx) => i18n.page_label(&config.pages[*idx]),
+            Route::Artworks => i18n.label("route-artworks"),
+            Route::Resume => i18n.label("route-resume"),
+            Route::Blog => i18n.blog_label(config),
+            Route::Links => i18n.label("route-links"),
         }
     }
 
+    pub fn update_browser_url(&self, config: &Config) {
+        self.update_browser_url_with_section(config, None);
+    }
+
     #[cfg(target_arch = "wasm32")]
-    pub fn update_browser_url(&self) {
+    pub fn update_browser_url_with_section(&self, config: &Config, section: Option<&str>) {
         if let Some(window) = web_sys::window() {
             if let Some(history) = window.history().ok() {
-                let hash = self.to_hash();
+                let hash = match section {
+                    Some(section) => format!("{}/{}", self.to_hash(config), section),
+                    None => self.to_hash(config),
+                };
                 let _ = history.replace_state_with_url(
                     &wasm_bindgen::JsValue::NULL,
                     "",
-                    Some(hash),
+                    Some(&hash),
                 );
             }
         }
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn update_browser_url(&self) {
+    pub fn update_browser_url_with_section(&self, _config: &Config, _section: Option<&str>) {
         // No-op on native
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_split_into_route_and_section() {
+        let cases = [
+            ("#/resume", ("#/resume", None)),
+            ("#/resume/", ("#/resume", None)),
+            ("#/resume/work-history", ("#/resume", Some("work-history"))),
+            ("#/resume/éducation", ("#/resume", Some("éducation"))),
+            ("#/blog/2024/hello", ("#/blog", Some("2024/hello"))),
+            ("#/", ("#/", None)),
+            ("", ("", None)),
+            ("resume/work", ("resume", Some("work"))),
+        ];
+        for (hash, expected) in cases {
+            assert_eq!(Route::split_section(hash), expected, "{hash:?}");
+        }
+    }
+
+    #[test]
+    fn hashes_name_routes() {
+        let config = Config::default();
+        let home = Route::home(&config);
+        assert_eq!(Route::from_hash("", &config), Some(home));
+        assert_eq!(Route::from_hash("#/", &config), Some(home));
+        assert_eq!(Route::from_hash("#/resume/work", &config), Some(Route::Resume));
+        assert_eq!(Route::from_hash("#/artworks", &config), Some(Route::Artworks));
+        assert_eq!(Route::from_hash("#/about", &config), Route::from_slug("about", &config));
+        assert_eq!(Route::from_hash("#/nowhere", &config), None);
+    }
+}
@@ -0,0 +1,257 @@
+use ammonia::{Url, UrlRelative};
+use pulldown_cmark::{Event, Parser, Tag};
+use std::collections::HashSet;
+use std::ops::Range;
+
+use crate::config::SanitizeConfig;
+
+/// Tags ammonia strips together with their content; they can't be allowed
+const CLEAN_CONTENT_TAGS: [&str; 2] = ["script", "style"];
+
+/// Applies the `[sanitize]` policy to a document fetched from `base_url`
+pub struct Sanitizer<'a> {
+    config: &'a SanitizeConfig,
+    base_url: Option<Url>,
+}
+
+impl<'a> Sanitizer<'a> {
+    pub fn new(config: &'a SanitizeConfig, base_url: &str) -> Self {
+        Self {
+            config,
+            base_url: Url::parse(base_url).ok(),
+        }
+    }
+
+    /// Clean HTML with an ammonia `Builder` configured from the policy
+    pub fn clean_html(&self, html: &str) -> String {
+        let mut builder = ammonia::Builder::default();
+
+        builder.url_schemes(self.config.url_schemes.iter().map(String::as_str).collect());
+
+        if let Some(tags) = &self.config.allowed_tags {
+            let tags: HashSet<&str> = tags
+                .iter()
+                .map(String::as_str)
+                .filter(|tag| !CLEAN_CONTENT_TAGS.contains(tag))
+                .collect();
+            builder.tags(tags);
+        }
+
+        if let (true, Some(base_url)) = (self.config.rewrite_relative_links, &self.base_url) {
+            builder.url_relative(UrlRelative::RewriteWithBase(base_url.clone()));
+        }
+
+        builder.clean(html).to_string()
+    }
+
+    /// Replace links and images whose URL isn't allowed with their plain text.
+    /// Needed on top of `clean_html` because escaped text can turn back into
+    /// markdown links once converted, and markdown resumes are never HTML.
+    pub fn clean_markdown(&self, markdown: &str) -> String {
+        let mut edits: Vec<(Range<usize>, Range<usize>)> = Vec::new();
+        // The link being dropped, and the source range of its text
+        let mut dropping: Option<(Range<usize>, Option<Range<usize>>)> = None;
+
+        for (event, range) in Parser::new(markdown).into_offset_iter() {
+            if let Some((link, text)) = &mut dropping {
+                if range.start >= link.start && range.end <= link.end {
+                    // Everything nested inside the link, but not its own end tag
+                    if range != *link {
+                        let start = text.as_ref().map_or(range.start, |t| t.start.min(range.start));
+                        let end = text.as_ref().map_or(range.end, |t| t.end.max(range.end));
+                        *text = Some(start..end);
+                    }
+                    continue;
+                }
+                let (link, text) = dropping.take().expect("checked above");
+                edits.push((link, text.unwrap_or(0..0)));
+            }
+
+            match event {
+                Event::Start(Tag::Link { dest_url, .. }) | Event::Start(Tag::Image { dest_url, .. })
+                    if !self.is_allowed_url(&dest_url) =>
+                {
+                    dropping = Some((range, None));
+                }
+                _ => {}
+            }
+        }
+        if let Some((link, text)) = dropping {
+            edits.push((link, text.unwrap_or(0..0)));
+        }
+
+        let mut cleaned = String::with_capacity(markdown.len());
+        let mut last = 0;
+        for (link, text) in edits {
+            cleaned.push_str(&markdown[last..link.start]);
+            cleaned.push_str(&markdown[text]);
+            last = link.end;
+        }
+        cleaned.push_str(&markdown[last..]);
+        cleaned
+    }
+
+    pub fn rewrites_relative_links(&self) -> bool {
+        self.config.rewrite_relative_links
+    }
+
+    /// Relative URLs are always allowed; absolute ones need an allowed scheme
+    pub fn is_allowed_url(&self, url: &str) -> bool {
+        // Browsers ignore whitespace and control characters inside URLs
+        let url: String = url
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+            .collect();
+
+        match scheme(&url) {
+            Some(scheme) => self
+                .config
+                .url_schemes
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(scheme)),
+            None => true,
+        }
+    }
+}
+
+/// The scheme of an absolute URL, e.g. `https` or `javascript`
+fn scheme(url: &str) -> Option<&str> {
+    let (scheme, _) = url.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next()?.is_ascii_alphabetic()
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then_some(scheme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::links::LinkResolver;
+    use crate::resume::{ResumeDocument, ResumeFormat};
+
+    const BASE_URL: &str = "https://resume.example.com/cv/";
+    const WEBSITE_URL: &str = "https://example.com";
+
+    fn parse(format: ResumeFormat, body: &str, sanitizer: &Sanitizer) -> ResumeDocument {
+        ResumeDocument::parse(format, body, sanitizer, &LinkResolver::new(BASE_URL, WEBSITE_URL)).unwrap()
+    }
+
+    /// Hostile pages run through the whole HTML → markdown pipeline
+    const FIXTURES: [(&str, &str); 7] = [
+        ("script_and_style", include_str!("../tests/fixtures/hostile/script_and_style.html")),
+        ("javascript_links", include_str!("../tests/fixtures/hostile/javascript_links.html")),
+        ("event_handlers", include_str!("../tests/fixtures/hostile/event_handlers.html")),
+        ("data_and_vbscript", include_str!("../tests/fixtures/hostile/data_and_vbscript.html")),
+        ("markdown_injection", include_str!("../tests/fixtures/hostile/markdown_injection.html")),
+        ("malformed", include_str!("../tests/fixtures/hostile/malformed.html")),
+        ("relative_links", include_str!("../tests/fixtures/hostile/relative_links.html")),
+    ];
+
+    fn link_targets(markdown: &str) -> Vec<String> {
+        Parser::new(markdown)
+            .filter_map(|event| match event {
+                Event::Start(Tag::Link { dest_url, .. }) | Event::Start(Tag::Image { dest_url, .. }) => {
+                    Some(dest_url.to_string())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn parse_html(html: &str, config: &SanitizeConfig) -> String {
+        let sanitizer = Sanitizer::new(config, BASE_URL);
+        match parse(ResumeFormat::Html, html, &sanitizer) {
+            ResumeDocument::Markdown(markdown) => markdown,
+            ResumeDocument::Json(_) => unreachable!("HTML always becomes markdown"),
+        }
+    }
+
+    #[test]
+    fn hostile_fixtures_only_keep_allowed_links() {
+        let config = SanitizeConfig::default();
+        let sanitizer = Sanitizer::new(&config, BASE_URL);
+
+        for (name, html) in FIXTURES {
+            let markdown = parse_html(html, &config);
+            for target in link_targets(&markdown) {
+                assert!(sanitizer.is_allowed_url(&target), "{name}: kept {target:?}");
+                assert!(!target.contains("javascript"), "{name}: kept {target:?}");
+            }
+
+            let lowercase = markdown.to_lowercase();
+            for needle in ["<script", "onerror", "onload", "<style", "<iframe"] {
+                assert!(!lowercase.contains(needle), "{name}: found {needle:?} in {markdown:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn safe_content_survives() {
+        let markdown = parse_html(FIXTURES[1].1, &SanitizeConfig::default());
+        assert!(link_targets(&markdown).contains(&"https://example.com/".to_string()));
+        assert!(markdown.contains("Portfolio"));
+    }
+
+    #[test]
+    fn relative_links_are_rewritten_against_the_base_url() {
+        let markdown = parse_html(FIXTURES[6].1, &SanitizeConfig::default());
+        let targets = link_targets(&markdown);
+        assert!(targets.contains(&"https://resume.example.com/about".to_string()), "{targets:?}");
+        assert!(targets.contains(&"https://resume.example.com/cv/projects.html".to_string()), "{targets:?}");
+    }
+
+    #[test]
+    fn relative_links_pass_through_when_disabled() {
+        let config = SanitizeConfig {
+            rewrite_relative_links: false,
+            ..Default::default()
+        };
+        let targets = link_targets(&parse_html(FIXTURES[6].1, &config));
+        assert!(targets.contains(&"/about".to_string()), "{targets:?}");
+    }
+
+    #[test]
+    fn allowed_tags_narrow_the_policy() {
+        let config = SanitizeConfig {
+            allowed_tags: Some(vec!["p".to_string(), "script".to_string()]),
+            ..Default::default()
+        };
+        let sanitizer = Sanitizer::new(&config, BASE_URL);
+        let html = sanitizer.clean_html("<p><b>bold</b> <a href=\"https://example.com\">link</a></p><script>alert(1)</script>");
+        assert_eq!(html, "<p>bold link</p>");
+    }
+
+    #[test]
+    fn markdown_links_with_unsafe_schemes_become_text() {
+        let config = SanitizeConfig::default();
+        let sanitizer = Sanitizer::new(&config, BASE_URL);
+        let markdown = "See [my *site*](javascript:alert(1)), ![pic](vbscript:x) and [ok](https://example.com).\n\n<javascript:alert(2)>\n\n[ref]\n\n[ref]: JaVaScRiPt:alert(3)\n";
+        let cleaned = sanitizer.clean_markdown(markdown);
+
+        assert_eq!(link_targets(&cleaned), vec!["https://example.com".to_string()]);
+        assert!(cleaned.contains("See my *site*, pic and [ok](https://example.com)."), "{cleaned:?}");
+    }
+
+    #[test]
+    fn markdown_resumes_are_cleaned() {
+        let config = SanitizeConfig::default();
+        let sanitizer = Sanitizer::new(&config, BASE_URL);
+        let document = parse(ResumeFormat::Markdown, "[x](javascript:alert(1))", &sanitizer);
+        let ResumeDocument::Markdown(markdown) = document else {
+            unreachable!("markdown stays markdown");
+        };
+        assert!(link_targets(&markdown).is_empty());
+    }
+
+    #[test]
+    fn obfuscated_schemes_are_rejected() {
+        let config = SanitizeConfig::default();
+        let sanitizer = Sanitizer::new(&config, BASE_URL);
+        for url in ["javascript:alert(1)", " JavaScript:alert(1)", "java\tscript:alert(1)", "java\nscript:x", "data:text/html,<b>"] {
+            assert!(!sanitizer.is_allowed_url(url), "{url:?}");
+        }
+        for url in ["https://example.com", "mailto:me@example.com", "/about", "#work", "notes.html"] {
+            assert!(sanitizer.is_allowed_url(url), "{url:?}");
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! The native window: an icon made from the site's name, and a remembered
+//! position kept on a monitor that is still connected.
+
+use eframe::egui;
+use std::sync::{Arc, Mutex};
+use winit::application::ApplicationHandler;
+use winit::event_loop::ActiveEventLoop;
+
+/// Width and height of the window icon, in pixels
+const ICON_SIZE: usize = 64;
+
+/// Corner radius of the icon's background, in pixels
+const ICON_RADIUS: f32 = 14.0;
+
+/// Height of the letter on the icon, as a share of its size
+const LETTER_HEIGHT: f32 = 0.56;
+
+/// Samples per pixel along each axis, for smooth edges
+const SUBSAMPLES: usize = 4;
+
+/// How much of the window, in points, must be on a monitor to be reachable
+const VISIBLE_MARGIN: f32 = 48.0;
+
+/// Monitors by their area in points, filled in once the event loop starts
+pub type Monitors = Arc<Mutex<Vec<egui::Rect>>>;
+
+/// eframe's event handling, with the monitors noted before the window is
+/// created so `keep_on_screen` can use them
+pub struct WithMonitors<A> {
+    app: A,
+    monitors: Monitors,
+}
+
+impl<A> WithMonitors<A> {
+    pub fn new(app: A, monitors: Monitors) -> Self {
+        Self { app, monitors }
+    }
+}
+
+impl<A: ApplicationHandler<eframe::UserEvent>> ApplicationHandler<eframe::UserEvent> for WithMonitors<A> {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if let Ok(mut monitors) = self.monitors.lock() {
+            // Primary first, so it is preferred when nothing else fits
+            let primary = event_loop.primary_monitor();
+            let mut handles: Vec<_> = event_loop.available_monitors().collect();
+            handles.sort_by_key(|handle| Some(handle) != primary.as_ref());
+            *monitors = handles
+                .iter()
+                .map(|handle| {
+                    let scale = handle.scale_factor() as f32;
+                    let position = handle.position();
+                    let size = handle.size();
+                    egui::Rect::from_min_size(
+                        egui::pos2(position.x as f32, position.y as f32) / scale,
+                        egui::vec2(size.width as f32, size.height as f32) / scale,
+                    )
+                })
+                .collect();
+        }
+        self.app.resumed(event_loop);
+    }
+
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: winit::event::StartCause) {
+        self.app.new_events(event_loop, cause);
+    }
+
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: eframe::UserEvent) {
+        self.app.user_event(event_loop, event);
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: winit::window::WindowId,
+        event: winit::event::WindowEvent,
+    ) {
+        self.app.window_event(event_loop, window_id, event);
+    }
+
+    fn device_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        device_id: winit::event::DeviceId,
+        event: winit::event::DeviceEvent,
+    ) {
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+    }
+
+    fn suspended(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    fn exiting(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+}
+
+/// Move a window that would open off every monitor, e.g. one last seen on a
+/// monitor since disconnected, onto the nearest one
+pub fn keep_on_screen(viewport: egui::ViewportBuilder, monitors: &[egui::Rect]) -> egui::ViewportBuilder {
+    let (Some(position), Some(size)) = (viewport.position, viewport.inner_size) else {
+        return viewport;
+    };
+    let position = on_screen(position, size, monitors);
+    viewport.with_position(position)
+}
+
+fn on_screen(position: egui::Pos2, size: egui::Vec2, monitors: &[egui::Rect]) -> egui::Pos2 {
+    let window = egui::Rect::from_min_size(position, size);
+    let margin = size.min(egui::Vec2::splat(VISIBLE_MARGIN));
+    let visible = |monitor: &egui::Rect| {
+        let overlap = monitor.intersect(window);
+        overlap.width() >= margin.x && overlap.height() >= margin.y
+    };
+    if monitors.is_empty() || monitors.iter().any(visible) {
+        return position;
+    }
+
+    let distance = |monitor: &egui::Rect| monitor.distance_sq_to_pos(window.center());
+    let nearest = monitors
+        .iter()
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        .expect("Monitors aren't empty");
+    egui::pos2(
+        position.x.clamp(nearest.min.x, (nearest.max.x - size.x).max(nearest.min.x)),
+        position.y.clamp(nearest.min.y, (nearest.max.y - size.y).max(nearest.min.y)),
+    )
+}
+
+/// The first letter of `name` on a rounded square, colored after the name
+pub fn icon(name: &str) -> egui::IconData {
+    let letter = name
+        .chars()
+        .find(|c| c.is_alphanumeric())
+        .and_then(|c| c.to_uppercase().next())
+        .unwrap_or('?');
+    let hue = name.bytes().fold(0u32, |hash, byte| hash.wrapping_mul(31).wrapping_add(u32::from(byte))) % 360;
+    let background = egui::Color32::from(egui::ecolor::Hsva::new(hue as f32 / 360.0, 0.55, 0.6, 1.0));
+    let edges = letter_edges(letter);
+
+    let mut rgba = Vec::with_capacity(ICON_SIZE * ICON_SIZE * 4);
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let (mut square, mut ink) = (0, 0);
+            for sy in 0..SUBSAMPLES {
+                for sx in 0..SUBSAMPLES {
+                    let p = egui::pos2(
+                        x as f32 + (sx as f32 + 0.5) / SUBSAMPLES as f32,
+                        y as f32 + (sy as f32 + 0.5) / SUBSAMPLES as f32,
+                    );
+                    if in_rounded_square(p) {
+                        square += 1;
+                        ink += usize::from(winding(&edges, p) != 0);
+                    }
+                }
+            }
+            let samples = (SUBSAMPLES * SUBSAMPLES) as f32;
+            let ink = ink as f32 / samples;
+            let color = |channel: u8| (channel as f32 * (1.0 - ink) + 255.0 * ink).round() as u8;
+            rgba.extend([
+                color(background.r()),
+                color(background.g()),
+                color(background.b()),
+                (255.0 * square as f32 / samples).round() as u8,
+            ]);
+        }
+    }
+
+    egui::IconData {
+        rgba,
+        width: ICON_SIZE as u32,
+        height: ICON_SIZE as u32,
+    }
+}
+
+fn in_rounded_square(p: egui::Pos2) -> bool {
+    let size = ICON_SIZE as f32;
+    let nearest_inner = egui::pos2(
+        p.x.clamp(ICON_RADIUS, size - ICON_RADIUS),
+        p.y.clamp(ICON_RADIUS, size - ICON_RADIUS),
+    );
+    p.distance(nearest_inner) <= ICON_RADIUS
+}
+
+/// Outline of `letter` in egui's default font as line segments in icon
+/// pixels, centered; empty when no font has the letter
+fn letter_edges(letter: char) -> Vec<[egui::Pos2; 2]> {
+    let definitions = egui::FontDefinitions::default();
+    let fonts = definitions
+        .families
+        .get(&egui::FontFamily::Proportional)
+        .into_iter()
+        .flatten()
+        .filter_map(|name| definitions.font_data.get(name));
+
+    for data in fonts {
+        let Ok(face) = ttf_parser::Face::parse(&data.font, data.index) else {
+            continue;
+        };
+        let Some(glyph) = face.glyph_index(letter) else {
+            continue;
+        };
+        let mut outline = Outline::default();
+        let Some(bounds) = face.outline_glyph(glyph, &mut outline) else {
+            continue;
+        };
+
+        // Font units are y-up; fit the glyph's height and center it
+        let (width, height) = (bounds.width() as f32, bounds.height() as f32);
+        let scale = LETTER_HEIGHT * ICON_SIZE as f32 / height.max(1.0);
+        let offset = egui::vec2(
+            (ICON_SIZE as f32 - width * scale) / 2.0,
+            (ICON_SIZE as f32 - height * scale) / 2.0,
+        );
+        let to_icon = |p: egui::Pos2| {
+            egui::pos2(
+                (p.x - bounds.x_min as f32) * scale + offset.x,
+                (bounds.y_max as f32 - p.y) * scale + offset.y,
+            )
+        };
+        return outline.edges.iter().map(|[a, b]| [to_icon(*a), to_icon(*b)]).collect();
+    }
+    Vec::new()
+}
+
+/// Nonzero winding number of the outline around `p`
+fn winding(edges: &[[egui::Pos2; 2]], p: egui::Pos2) -> i32 {
+    let mut winding = 0;
+    for [a, b] in edges {
+        if (a.y <= p.y) != (b.y <= p.y) {
+            let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if x > p.x {
+                winding += if b.y > a.y { 1 } else { -1 };
+            }
+        }
+    }
+    winding
+}
+
+/// Glyph outline flattened into line segments, in font units
+#[derive(Default)]
+struct Outline {
+    edges: Vec<[egui::Pos2; 2]>,
+    start: egui::Pos2,
+    last: egui::Pos2,
+}
+
+impl Outline {
+    /// Segments per curve
+    const CURVE_STEPS: usize = 8;
+
+    fn line(&mut self, to: egui::Pos2) {
+        self.edges.push([self.last, to]);
+        self.last = to;
+    }
+}
+
+impl ttf_parser::OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = egui::pos2(x, y);
+        self.last = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.line(egui::pos2(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (p0, p1, p2) = (self.last.to_vec2(), egui::vec2(x1, y1), egui::vec2(x, y));
+        for step in 1..=Self::CURVE_STEPS {
+            let t = step as f32 / Self::CURVE_STEPS as f32;
+            let u = 1.0 - t;
+            self.line((p0 * u * u + p1 * 2.0 * u * t + p2 * t * t).to_pos2());
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p0, p1, p2, p3) = (self.last.to_vec2(), egui::vec2(x1, y1), egui::vec2(x2, y2), egui::vec2(x, y));
+        for step in 1..=Self::CURVE_STEPS {
+            let t = step as f32 / Self::CURVE_STEPS as f32;
+            let u = 1.0 - t;
+            self.line((p0 * u * u * u + p1 * 3.0 * u * u * t + p2 * 3.0 * u * t * t + p3 * t * t * t).to_pos2());
+        }
+    }
+
+    fn close(&mut self) {
+        if self.last != self.start {
+            self.line(self.start);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_off_every_monitor_are_moved_back() {
+        let laptop = egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(1440.0, 900.0));
+        let external = egui::Rect::from_min_size(egui::pos2(1440.0, 0.0), egui::vec2(1920.0, 1080.0));
+        let size = egui::vec2(800.0, 600.0);
+
+        // On the external monitor while it is connected
+        let remembered = egui::pos2(2000.0, 100.0);
+        assert_eq!(on_screen(remembered, size, &[laptop, external]), remembered);
+
+        // Pulled back to the laptop's right edge once it isn't
+        assert_eq!(on_screen(remembered, size, &[laptop]), egui::pos2(640.0, 100.0));
+
+        // Mostly off-screen but still reachable
+        let peeking = egui::pos2(-700.0, 50.0);
+        assert_eq!(on_screen(peeking, size, &[laptop]), peeking);
+        assert_eq!(on_screen(egui::pos2(-790.0, 50.0), size, &[laptop]), egui::pos2(0.0, 50.0));
+    }
+
+    #[test]
+    fn icon_shows_the_first_letter() {
+        let icon = icon("hwww");
+        assert_eq!((icon.width, icon.height), (ICON_SIZE as u32, ICON_SIZE as u32));
+        let pixel = |x: usize, y: usize| &icon.rgba[(y * ICON_SIZE + x) * 4..][..4];
+
+        // Transparent corners, opaque inside, and white where the letter is
+        assert_eq!(pixel(0, 0)[3], 0);
+        assert_eq!(pixel(ICON_SIZE / 2, 4)[3], 255);
+        let white = icon.rgba.chunks(4).filter(|p| p[..3] == [255, 255, 255]).count();
+        assert!(white > ICON_SIZE * ICON_SIZE / 20, "{} white pixels", white);
+    }
+}